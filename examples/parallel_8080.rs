@@ -0,0 +1,89 @@
+//! Bounce a square around the screen over the 8080 parallel bus, which is fast enough to redraw
+//! the whole frame every time.
+//!
+//! The parallel bus needs 11 GPIO pins: the data pins D0 to D7, D/C, WR and CS. The RD pin is
+//! tied high as the display is never read, and the panel must be configured for the 8080
+//! interface, usually with the BS0 to BS2 jumpers or resistors on its back.
+//!
+//! This example is for the STM32F103 "Blue Pill" board.
+//!
+//! Wiring connections are as follows:
+//!
+//! ```
+//!  Display -> Blue Pill
+//!      GND -> GND
+//!      VCC -> 3V3
+//!  D0...D7 -> PA0...PA7
+//!      D/C -> PB0
+//!       WR -> PB1
+//!       CS -> PB10
+//!       RD -> 3V3
+//!      RST -> PB11
+//! ```
+//!
+//! Run on a Blue Pill with `cargo run --example parallel_8080 --features parallel`.
+
+#![no_std]
+#![no_main]
+
+use cortex_m_rt::entry;
+use defmt_rtt as _;
+use embassy_stm32::gpio::{Level, Output, Speed};
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+};
+use panic_probe as _;
+use sh1106::{prelude::*, ParallelInterface, Sh1106};
+
+#[entry]
+fn main() -> ! {
+    let p = embassy_stm32::init(Default::default());
+
+    let bus = Generic8BitBus::new((
+        Output::new(p.PA0, Level::Low, Speed::VeryHigh),
+        Output::new(p.PA1, Level::Low, Speed::VeryHigh),
+        Output::new(p.PA2, Level::Low, Speed::VeryHigh),
+        Output::new(p.PA3, Level::Low, Speed::VeryHigh),
+        Output::new(p.PA4, Level::Low, Speed::VeryHigh),
+        Output::new(p.PA5, Level::Low, Speed::VeryHigh),
+        Output::new(p.PA6, Level::Low, Speed::VeryHigh),
+        Output::new(p.PA7, Level::Low, Speed::VeryHigh),
+    ));
+    let dc = Output::new(p.PB0, Level::Low, Speed::VeryHigh);
+    let wr = Output::new(p.PB1, Level::High, Speed::VeryHigh);
+    let cs = Output::new(p.PB10, Level::High, Speed::VeryHigh);
+    let mut rst = Output::new(p.PB11, Level::Low, Speed::Low);
+
+    let interface = ParallelInterface::new(bus, dc, wr, cs);
+    let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+        .into_buffered_graphics_mode();
+    display
+        .reset(&mut rst, &mut embassy_time::Delay {})
+        .unwrap();
+    display.init().unwrap();
+
+    let style = PrimitiveStyle::with_fill(BinaryColor::On);
+    let size = Size::new(16, 16);
+    let mut position = Point::zero();
+    let mut velocity = Point::new(2, 1);
+
+    loop {
+        display.clear_buffer();
+        Rectangle::new(position, size)
+            .into_styled(style)
+            .draw(&mut display)
+            .unwrap();
+        display.flush().unwrap();
+
+        position += velocity;
+
+        if position.x <= 0 || position.x + size.width as i32 >= 128 {
+            velocity.x = -velocity.x;
+        }
+        if position.y <= 0 || position.y + size.height as i32 >= 64 {
+            velocity.y = -velocity.y;
+        }
+    }
+}
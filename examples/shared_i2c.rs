@@ -0,0 +1,95 @@
+//! Show the temperature read from an LM75 sensor on the same I2C bus as the display, using the
+//! terminal mode.
+//!
+//! The bus is shared with `embedded_hal_bus::i2c::RefCellDevice`, which gives every driver its
+//! own handle implementing the `I2c` trait. `I2CDisplayInterface::new` accepts any `I2c`
+//! implementation, so the display takes one handle and the sensor is read through another.
+//! `AtomicDevice` and `CriticalSectionDevice` work the same way for buses shared between
+//! interrupt handlers or threads.
+//!
+//! This example is for the STM32F103 "Blue Pill" board using I2C1.
+//!
+//! Wiring connections are as follows, with the display and the sensor on the same wires:
+//!
+//! ```
+//!   Display and LM75 -> Blue Pill
+//!                GND -> GND
+//!                VCC -> 3V3
+//!                SDA -> PB7
+//!                SCL -> PB6
+//! ```
+//!
+//! The LM75 address pins A0 to A2 must be tied low for address 0x48.
+//!
+//! Run on a Blue Pill with `cargo run --example shared_i2c`.
+
+#![no_std]
+#![no_main]
+
+use core::{cell::RefCell, fmt::Write};
+use cortex_m_rt::entry;
+use defmt_rtt as _;
+use embassy_stm32::time::Hertz;
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+use embedded_hal_bus::i2c::RefCellDevice;
+use panic_probe as _;
+use sh1106::{prelude::*, I2CDisplayInterface, Sh1106};
+
+/// I2C address of the LM75 with A0 to A2 tied low
+const LM75_ADDRESS: u8 = 0x48;
+
+/// Read the temperature of an LM75 in half degrees Celsius.
+fn read_temperature<I: I2c>(i2c: &mut I) -> Result<i16, I::Error> {
+    let mut bytes = [0; 2];
+
+    // Register 0 holds the temperature as a left-aligned 9 bit two's complement value
+    i2c.write_read(LM75_ADDRESS, &[0x00], &mut bytes)?;
+
+    Ok(i16::from_be_bytes(bytes) >> 7)
+}
+
+#[entry]
+fn main() -> ! {
+    let p = embassy_stm32::init(Default::default());
+    let i2c = embassy_stm32::i2c::I2c::new_blocking(
+        p.I2C1,
+        p.PB6,
+        p.PB7,
+        Hertz::khz(400),
+        Default::default(),
+    );
+    let bus = RefCell::new(i2c);
+
+    let interface = I2CDisplayInterface::new(RefCellDevice::new(&bus));
+    let mut display =
+        Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0).into_terminal_mode();
+    display.init().unwrap();
+    let _ = display.clear();
+
+    let mut sensor = RefCellDevice::new(&bus);
+    let mut delay = embassy_time::Delay {};
+
+    loop {
+        let _ = display.set_position(0, 0);
+
+        match read_temperature(&mut sensor) {
+            Ok(half_degrees) => {
+                let sign = if half_degrees < 0 { "-" } else { " " };
+                let half_degrees = half_degrees.unsigned_abs();
+
+                let _ = write!(
+                    display,
+                    "Temp: {}{}.{} C",
+                    sign,
+                    half_degrees / 2,
+                    (half_degrees % 2) * 5
+                );
+            }
+            Err(_) => {
+                let _ = display.write_str("No sensor     ");
+            }
+        }
+
+        delay.delay_ms(1000);
+    }
+}
@@ -0,0 +1,111 @@
+//! Print "Hello world!" over a 3-wire SPI interface, for panels without a D/C pin. Uses the
+//! `embedded_graphics` crate to draw the text with a 6x10 pixel font.
+//!
+//! 3-wire SPI sends every byte as a 9 bit word. The SPI peripheral of the STM32F103 only supports
+//! 8 and 16 bit frames, so this example bit-bangs the 9 bit words on GPIO pins instead. On a chip
+//! whose SPI peripheral supports 9 bit frames, pass its `SpiDevice<u16>` to
+//! `SPIDisplayInterface::new_3wire` directly.
+//!
+//! This example is for the STM32F103 "Blue Pill" board.
+//!
+//! Wiring connections are as follows:
+//!
+//! ```
+//!     Display -> Blue Pill
+//!         GND -> GND
+//!         VCC -> 3V3
+//!   D0 (SCK)  -> PA5
+//!   D1 (MOSI) -> PA7
+//!         RST -> PB0
+//!          CS -> PB10
+//! ```
+//!
+//! The panel must be configured for 3-wire SPI, usually with the BS0 to BS2 jumpers or resistors
+//! on its back.
+//!
+//! Run on a Blue Pill with `cargo run --example spi_3wire`.
+
+#![no_std]
+#![no_main]
+
+use core::convert::Infallible;
+use cortex_m::asm::nop;
+use cortex_m_rt::entry;
+use defmt_rtt as _;
+use embassy_stm32::gpio::{Level, Output, Speed};
+use embedded_graphics::{
+    mono_font::{ascii::FONT_6X10, MonoTextStyleBuilder},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Baseline, Text},
+};
+use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+use panic_probe as _;
+use sh1106::{prelude::*, SPIDisplayInterface, Sh1106};
+
+/// A bit-banged SPI device sending the low 9 bits of each word, MSB first, in SPI mode 0.
+struct BitBang9<'a> {
+    sck: Output<'a>,
+    mosi: Output<'a>,
+    cs: Output<'a>,
+}
+
+impl ErrorType for BitBang9<'_> {
+    type Error = Infallible;
+}
+
+impl SpiDevice<u16> for BitBang9<'_> {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u16>]) -> Result<(), Infallible> {
+        self.cs.set_low();
+
+        for operation in operations {
+            if let Operation::Write(words) = operation {
+                for word in words.iter() {
+                    for bit in (0..9).rev() {
+                        self.mosi.set_level(Level::from(word & (1 << bit) != 0));
+                        self.sck.set_high();
+                        self.sck.set_low();
+                    }
+                }
+            }
+        }
+
+        self.cs.set_high();
+
+        Ok(())
+    }
+}
+
+#[entry]
+fn main() -> ! {
+    let p = embassy_stm32::init(Default::default());
+
+    let mut rst = Output::new(p.PB0, Level::Low, Speed::Low);
+    let spi = BitBang9 {
+        sck: Output::new(p.PA5, Level::Low, Speed::VeryHigh),
+        mosi: Output::new(p.PA7, Level::Low, Speed::VeryHigh),
+        cs: Output::new(p.PB10, Level::High, Speed::VeryHigh),
+    };
+
+    let interface = SPIDisplayInterface::new_3wire(spi);
+    let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+        .into_buffered_graphics_mode();
+    display
+        .reset(&mut rst, &mut embassy_time::Delay {})
+        .unwrap();
+    display.init().unwrap();
+
+    let text_style = MonoTextStyleBuilder::new()
+        .font(&FONT_6X10)
+        .text_color(BinaryColor::On)
+        .build();
+
+    Text::with_baseline("Hello world!", Point::zero(), text_style, Baseline::Top)
+        .draw(&mut display)
+        .unwrap();
+
+    display.flush().unwrap();
+    loop {
+        nop()
+    }
+}
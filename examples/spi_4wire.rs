@@ -0,0 +1,72 @@
+//! Print "Hello world!" with "Hello rust!" underneath over a 4-wire SPI interface. Uses the
+//! `embedded_graphics` crate to draw the text with a 6x10 pixel font.
+//!
+//! This example is for the STM32F103 "Blue Pill" board using SPI1.
+//!
+//! Wiring connections are as follows:
+//!
+//! ```
+//!     Display -> Blue Pill
+//!         GND -> GND
+//!         VCC -> 3V3
+//!   D0 (SCK)  -> PA5
+//!   D1 (MOSI) -> PA7
+//!         RST -> PB0
+//!         D/C -> PB1
+//!          CS -> PB10
+//! ```
+//!
+//! Run on a Blue Pill with `cargo run --example spi_4wire`.
+
+#![no_std]
+#![no_main]
+
+use cortex_m::asm::nop;
+use cortex_m_rt::entry;
+use defmt_rtt as _;
+use embassy_stm32::{gpio, spi::Spi};
+use embedded_graphics::{
+    mono_font::{ascii::FONT_6X10, MonoTextStyleBuilder},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Baseline, Text},
+};
+use panic_probe as _;
+use sh1106::{prelude::*, SPIDisplayInterface, Sh1106};
+
+#[entry]
+fn main() -> ! {
+    let p = embassy_stm32::init(Default::default());
+    let spi = Spi::new_blocking_txonly(p.SPI1, p.PA5, p.PA7, Default::default());
+
+    let mut rst = gpio::Output::new(p.PB0, gpio::Level::Low, gpio::Speed::Low);
+    let dc = gpio::Output::new(p.PB1, gpio::Level::Low, gpio::Speed::Low);
+    let cs = gpio::Output::new(p.PB10, gpio::Level::High, gpio::Speed::Low);
+    let spi = embedded_hal_bus::spi::ExclusiveDevice::new_no_delay(spi, cs).unwrap();
+
+    let interface = SPIDisplayInterface::new(spi, dc);
+    let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+        .into_buffered_graphics_mode();
+    display
+        .reset(&mut rst, &mut embassy_time::Delay {})
+        .unwrap();
+    display.init().unwrap();
+
+    let text_style = MonoTextStyleBuilder::new()
+        .font(&FONT_6X10)
+        .text_color(BinaryColor::On)
+        .build();
+
+    Text::with_baseline("Hello world!", Point::zero(), text_style, Baseline::Top)
+        .draw(&mut display)
+        .unwrap();
+
+    Text::with_baseline("Hello Rust!", Point::new(0, 16), text_style, Baseline::Top)
+        .draw(&mut display)
+        .unwrap();
+
+    display.flush().unwrap();
+    loop {
+        nop()
+    }
+}
@@ -0,0 +1,121 @@
+//! Display RAM addressing used when flushing.
+//!
+//! The SH1106 only supports page addressing, so before each run of data is written the driver
+//! sends the page and column address it starts at. Some "SH1106 compatible" controllers don't
+//! follow the datasheet exactly, for example wrapping the column address early. An
+//! [`AddressingStrategy`] can be set with [`Sh1106::with_addressing`](crate::Sh1106::with_addressing)
+//! to change how the address is sent and how long each run may be, without changing the rest of
+//! the flush.
+//!
+//! ```rust
+//! # use sh1106::test_helpers::MockInterface;
+//! # let interface = MockInterface::new();
+//! use sh1106::{
+//!     addressing::{AddressingStrategy, PageAddressing},
+//!     command::Page,
+//!     prelude::*,
+//!     Sh1106,
+//! };
+//!
+//! /// A controller which wraps to the next page after RAM column 63
+//! #[derive(Debug)]
+//! struct SplitHalves;
+//!
+//! impl AddressingStrategy for SplitHalves {
+//!     fn address(&self, page: Page, column: u8, commands: &mut [u8; 4]) -> usize {
+//!         PageAddressing.address(page, column, commands)
+//!     }
+//!
+//!     fn run_length(&self, column: u8, len: usize) -> usize {
+//!         match column {
+//!             0..=63 => len.min(64 - column as usize),
+//!             _ => len,
+//!         }
+//!     }
+//! }
+//!
+//! let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+//!     .with_addressing(&SplitHalves)
+//!     .into_buffered_graphics_mode();
+//!
+//! display.set_pixel(60, 0, true);
+//! display.set_pixel(70, 0, true);
+//! display.flush().unwrap();
+//!
+//! // Columns 60 to 70 start at RAM column 62, and are split after RAM column 63
+//! assert_eq!(
+//!     display.release().commands(),
+//!     &[0xB0, 0x0E, 0x13, 0xB0, 0x00, 0x14]
+//! );
+//! ```
+
+use crate::command::Page;
+use core::fmt::Debug;
+
+/// How the display RAM address is set before each run of data sent by a flush.
+///
+/// The driver keeps a `&'static` reference to the strategy, so it must be `Sync` for the driver
+/// to stay `Send`, for example to share it in a `Mutex` or an RTIC resource.
+pub trait AddressingStrategy: Debug + Sync {
+    /// Write the command bytes which point the display RAM address at `column` of `page` into
+    /// `commands`, and return how many bytes were written.
+    ///
+    /// `column` is a display RAM column, with the column offset of the display already applied.
+    fn address(&self, page: Page, column: u8, commands: &mut [u8; 4]) -> usize;
+
+    /// Like [`address`](AddressingStrategy::address), with the page given as an index. Flushes
+    /// use this, so controllers with more than the 8 pages of the SH1106 can be addressed.
+    ///
+    /// The default converts the index with `Page::from`, which clamps pages past
+    /// [`Page::Page7`].
+    fn address_page(&self, page: u8, column: u8, commands: &mut [u8; 4]) -> usize {
+        self.address(Page::from(page.saturating_mul(8)), column, commands)
+    }
+
+    /// Get how many of the `len` bytes starting at display RAM `column` can be written before
+    /// the address has to be set again. Values outside `1..=len` are clamped.
+    ///
+    /// The default allows writing the whole run at once.
+    fn run_length(&self, _column: u8, len: usize) -> usize {
+        len
+    }
+}
+
+/// Page addressing as described in the datasheet, the default.
+///
+/// The page address is sent, followed by the lower and upper nibble of the column address, and
+/// the column address increments after every byte written.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct PageAddressing;
+
+impl AddressingStrategy for PageAddressing {
+    fn address(&self, page: Page, column: u8, commands: &mut [u8; 4]) -> usize {
+        commands[..3].copy_from_slice(&[0xB0 | page as u8, column & 0xF, 0x10 | (column >> 4)]);
+
+        3
+    }
+}
+
+/// Page addressing of the SH1107, a controller with 128 columns and 16 pages of display RAM,
+/// used by many 128x128 modules sold as SH1106 displays.
+///
+/// The SH1107 takes the same page and column address commands as the SH1106, but page addresses
+/// run from `0xB0` to `0xBF`. This is the addressing of
+/// [`DisplaySize128x128`](crate::size::DisplaySize128x128), and is available with the `sh1107`
+/// feature.
+#[cfg(feature = "sh1107")]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Sh1107Addressing;
+
+#[cfg(feature = "sh1107")]
+impl AddressingStrategy for Sh1107Addressing {
+    fn address(&self, page: Page, column: u8, commands: &mut [u8; 4]) -> usize {
+        self.address_page(page as u8, column, commands)
+    }
+
+    fn address_page(&self, page: u8, column: u8, commands: &mut [u8; 4]) -> usize {
+        commands[..3].copy_from_slice(&[0xB0 | (page & 0xF), column & 0xF, 0x10 | (column >> 4)]);
+
+        3
+    }
+}
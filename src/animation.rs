@@ -0,0 +1,173 @@
+//! Frame-by-frame animation.
+//!
+//! [`Animator`] codifies the "advance frame, draw, flush" loop of simple animations. It doesn't
+//! measure time; the application calls [`tick`](Animator::tick) whenever the next frame is due:
+//!
+//! ```rust
+//! # use sh1106::test_helpers::{StubInterface, DelayStub};
+//! # let interface = StubInterface;
+//! # let mut delay = DelayStub;
+//! use embedded_hal::delay::DelayNs;
+//! use sh1106::{animation::Animator, prelude::*, Sh1106};
+//!
+//! let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+//!     .into_buffered_graphics_mode();
+//!
+//! // A dot moving along the top row, starting again after 128 frames
+//! let mut animator = Animator::new(|display: &mut Sh1106Buffered<_, _>, frame| {
+//!     display.clear_buffer();
+//!     display.set_pixel(frame as u32, 0, true);
+//! })
+//! .with_frames(128);
+//!
+//! # for _ in 0..3 {
+//! animator.tick(&mut display).unwrap();
+//! delay.delay_ms(20);
+//! # }
+//! ```
+
+use crate::Error;
+#[cfg(feature = "async")]
+use crate::{mode::BufferedGraphicsModeAsync, size::DisplaySizeAsync, Sh1106Async};
+use crate::{
+    mode::{BufferedGraphicsMode, Shadow},
+    size::DisplaySize,
+    Sh1106,
+};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::WriteOnlyDataCommand;
+
+/// Animation driven by a drawing callback.
+///
+/// Each [`tick`](Animator::tick) calls the callback with the display and the current frame
+/// index, flushes the display and moves on to the next frame. The callback draws into the
+/// framebuffer of the [`BufferedGraphicsMode`] and may keep any state it needs in its captures.
+#[maybe_async_cfg::maybe(sync(keep_self), async(feature = "async"))]
+#[derive(Debug, Copy, Clone)]
+pub struct Animator<F> {
+    draw: F,
+    frame: usize,
+    frames: Option<usize>,
+    only_when_dirty: bool,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync"),
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            Sh1106(async = "Sh1106Async"),
+        )
+    )
+)]
+impl<F> Animator<F> {
+    /// Create a new animator starting at frame 0.
+    ///
+    /// The frame index counts up forever, wrapping around to 0 after `usize::MAX`. Use
+    /// [`with_frames`](Animator::with_frames) for an animation which loops.
+    pub fn new(draw: F) -> Self {
+        Self {
+            draw,
+            frame: 0,
+            frames: None,
+            only_when_dirty: false,
+        }
+    }
+
+    /// Loop through the frames `0..frames`, starting again at frame 0 after the last one. A
+    /// count of 0 leaves the frame index counting up forever.
+    pub fn with_frames(self, frames: usize) -> Self {
+        Self {
+            frames: (frames > 0).then_some(frames),
+            frame: if frames > 0 {
+                self.frame % frames
+            } else {
+                self.frame
+            },
+            ..self
+        }
+    }
+
+    /// Only flush when the callback changed the framebuffer, see
+    /// [`is_dirty`](Sh1106::is_dirty).
+    ///
+    /// [`flush`](Sh1106::flush) already sends nothing for an unchanged framebuffer, but still
+    /// counts as a flush for [`set_idle_dim`](Sh1106::set_idle_dim). Skipping it entirely keeps
+    /// frames which don't change the image from counting against the idle timeout.
+    pub fn only_when_dirty(self) -> Self {
+        Self {
+            only_when_dirty: true,
+            ..self
+        }
+    }
+
+    /// Get the index of the frame drawn by the next [`tick`](Animator::tick).
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Jump to `frame`, wrapping around if it is beyond the number of frames.
+    pub fn set_frame(&mut self, frame: usize) {
+        self.frame = match self.frames {
+            Some(frames) => frame % frames,
+            None => frame,
+        };
+    }
+
+    /// Draw the current frame with the callback, flush the display and advance to the next
+    /// frame.
+    ///
+    /// If the flush fails, the frame isn't advanced, so the next tick draws the same frame
+    /// again.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{animation::Animator, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // The first frame draws a pixel, the second leaves the framebuffer unchanged
+    /// let mut animator = Animator::new(|display: &mut Sh1106Buffered<_, _>, frame| {
+    ///     if frame == 0 {
+    ///         display.set_pixel(0, 0, true);
+    ///     }
+    /// })
+    /// .with_frames(2)
+    /// .only_when_dirty();
+    ///
+    /// for _ in 0..3 {
+    ///     animator.tick(&mut display).unwrap();
+    /// }
+    /// assert_eq!(animator.frame(), 1);
+    ///
+    /// // Frame 0 was flushed twice, frame 1 not at all
+    /// assert_eq!(display.release().data(), &[0x01, 0x01]);
+    /// ```
+    pub async fn tick<DI, SIZE, SHADOW, BUF>(
+        &mut self,
+        display: &mut Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&mut Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>, usize),
+        DI: WriteOnlyDataCommand,
+        SIZE: DisplaySize,
+        SHADOW: Shadow,
+        BUF: AsMut<[u8]> + AsRef<[u8]>,
+    {
+        (self.draw)(display, self.frame);
+
+        if !self.only_when_dirty || display.is_dirty() {
+            display.flush().await?;
+        }
+
+        self.set_frame(self.frame.wrapping_add(1));
+
+        Ok(())
+    }
+}
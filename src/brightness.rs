@@ -51,3 +51,18 @@ impl Brightness {
         }
     }
 }
+
+/// Contrast for each brightness percentage, `round(255 * (percent / 100) ^ 2.2)`
+const PERCENT_CONTRAST: [u8; 101] = [
+    0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 3, 3, 4, 5, 5, 6, 7, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    17, 18, 19, 21, 22, 24, 25, 27, 29, 30, 32, 34, 36, 38, 40, 42, 44, 46, 48, 51, 53, 55, 58, 60,
+    63, 66, 68, 71, 74, 77, 80, 83, 86, 89, 92, 96, 99, 102, 106, 109, 113, 116, 120, 124, 128,
+    131, 135, 139, 143, 148, 152, 156, 160, 165, 169, 174, 178, 183, 188, 192, 197, 202, 207, 212,
+    217, 223, 228, 233, 238, 244, 249, 255,
+];
+
+/// Map a brightness percentage onto the contrast register with a gamma 2.2 curve, so equal steps
+/// in percent look evenly spaced. Values above 100 are clamped.
+pub(crate) fn percent_to_contrast(percent: u8) -> u8 {
+    PERCENT_CONTRAST[percent.min(100) as usize]
+}
@@ -0,0 +1,146 @@
+//! Driver builder
+
+use crate::{
+    config::{InitConfig, PanelProfile},
+    mode::BasicMode,
+    rotation::DisplayRotation,
+    size::{DisplaySize, DisplaySize128x64},
+    Sh1106,
+};
+#[cfg(feature = "async")]
+use crate::{size::DisplaySizeAsync, Sh1106Async};
+
+/// Builder for an SH1106 driver.
+///
+/// The builder starts out with a 128x64 display, no rotation and the column offset of the display
+/// size. [`connect`](Builder::connect) creates a driver in [`BasicMode`], which still needs to be
+/// initialised.
+///
+/// ```rust
+/// # use sh1106::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use sh1106::{prelude::*, Builder};
+///
+/// let mut display = Builder::new()
+///     .size(DisplaySize128x32)
+///     .rotation(DisplayRotation::Rotate180)
+///     .column_offset(0)
+///     .connect(interface)
+///     .into_buffered_graphics_mode();
+///
+/// display.init().unwrap();
+/// ```
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(feature = "async", idents(Builder(async = "BuilderAsync")))
+)]
+#[derive(Debug, Copy, Clone)]
+pub struct Builder<SIZE = DisplaySize128x64> {
+    size: SIZE,
+    rotation: DisplayRotation,
+    /// Column offset, or `None` to use the offset of the display size
+    column_offset: Option<u8>,
+    /// Initialisation settings, or `None` to use the defaults of the display size
+    profile: Option<InitConfig>,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(feature = "async", idents(Builder(async = "BuilderAsync")))
+)]
+impl Builder {
+    /// Create a new builder for a 128x64 display with no rotation.
+    pub fn new() -> Self {
+        Self {
+            size: DisplaySize128x64,
+            rotation: DisplayRotation::Rotate0,
+            column_offset: None,
+            profile: None,
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(feature = "async", idents(Builder(async = "BuilderAsync")))
+)]
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            Builder(async = "BuilderAsync"),
+            DisplaySize(async = "DisplaySizeAsync"),
+            Sh1106(async = "Sh1106Async"),
+        )
+    )
+)]
+impl<SIZE> Builder<SIZE>
+where
+    SIZE: DisplaySize,
+{
+    /// Set the size of the display.
+    pub fn size<SIZE2>(self, size: SIZE2) -> Builder<SIZE2>
+    where
+        SIZE2: DisplaySize,
+    {
+        Builder {
+            size,
+            rotation: self.rotation,
+            column_offset: self.column_offset,
+            profile: self.profile,
+        }
+    }
+
+    /// Set the rotation of the display.
+    pub fn rotation(self, rotation: DisplayRotation) -> Self {
+        Self { rotation, ..self }
+    }
+
+    /// Override the display RAM column offset of the display size. See
+    /// [`Sh1106::with_column_offset`] for more information.
+    pub fn column_offset(self, offset: u8) -> Self {
+        Self {
+            column_offset: Some(offset),
+            ..self
+        }
+    }
+
+    /// Set the panel profile sent when the display is initialised. See
+    /// [`Sh1106::with_profile`] for more information.
+    pub fn profile(self, profile: PanelProfile) -> Self {
+        Self {
+            profile: Some(profile.init_config()),
+            ..self
+        }
+    }
+
+    /// Create a driver for the display connected to `interface`.
+    ///
+    /// Nothing is sent to the display until it is initialised.
+    pub fn connect<DI>(self, interface: DI) -> Sh1106<DI, SIZE, BasicMode> {
+        Sh1106 {
+            interface,
+            mode: BasicMode,
+            size: self.size,
+            rotation: self.rotation,
+            column_offset: self.column_offset.unwrap_or(SIZE::OFFSETX),
+            addressing: SIZE::addressing(),
+            profile: self.profile,
+            mirror: (false, false),
+            contrast: self.profile.map_or(SIZE::default_contrast(), |config| {
+                config.brightness.contrast
+            }),
+            start_line: 0,
+            invert: false,
+            display_on: false,
+            height: SIZE::HEIGHT,
+        }
+    }
+}
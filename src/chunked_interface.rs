@@ -0,0 +1,104 @@
+//! Display interface wrapper limiting the length of data transfers
+
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{
+    DataFormat::{self, U8},
+    DisplayError, WriteOnlyDataCommand,
+};
+
+/// A display interface which splits display data into transfers of at most `chunk_size` bytes.
+///
+/// A full flush of a 128x64 display writes a whole page of 128 bytes in a single
+/// [`send_data`](WriteOnlyDataCommand::send_data) call.
+/// Some HALs can't write that much at once, for example ones which copy every write into a fixed
+/// size internal buffer, or STM32 I2C HALs which don't split writes longer than the 255 bytes the
+/// peripheral can transfer in one go. Wrapping the interface limits how much is passed to it per
+/// call. Commands are passed through unchanged.
+///
+/// Use [`I2CDisplayInterface::with_chunk_size`](crate::I2CDisplayInterface::with_chunk_size) to
+/// create a chunked I2C interface.
+///
+/// ```rust
+/// # use sh1106::test_helpers::MockInterface;
+/// use sh1106::{prelude::*, ChunkedInterface, Sh1106};
+///
+/// let interface = ChunkedInterface::new(MockInterface::new(), 16);
+/// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// display.set_pixel(0, 0, true);
+/// display.set_pixel(40, 0, true);
+/// display.flush().unwrap();
+///
+/// // The data is the same, only split into more transfers
+/// assert_eq!(display.release().release().data().len(), 41);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ChunkedInterface<DI> {
+    interface: DI,
+    chunk_size: usize,
+}
+
+impl<DI> ChunkedInterface<DI> {
+    /// Wrap `interface`, sending at most `chunk_size` bytes of display data per transfer.
+    ///
+    /// A `chunk_size` of 0 is treated as 1.
+    pub fn new(interface: DI, chunk_size: usize) -> Self {
+        Self {
+            interface,
+            chunk_size: chunk_size.max(1),
+        }
+    }
+
+    /// Get the maximum number of display data bytes sent per transfer.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Release the wrapped interface.
+    pub fn release(self) -> DI {
+        self.interface
+    }
+}
+
+impl<DI> WriteOnlyDataCommand for ChunkedInterface<DI>
+where
+    DI: WriteOnlyDataCommand,
+{
+    fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.interface.send_commands(cmds)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        match buf {
+            U8(data) => data
+                .chunks(self.chunk_size)
+                .try_for_each(|chunk| self.interface.send_data(U8(chunk))),
+            buf => self.interface.send_data(buf),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<DI> AsyncWriteOnlyDataCommand for ChunkedInterface<DI>
+where
+    DI: AsyncWriteOnlyDataCommand,
+{
+    async fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.interface.send_commands(cmds).await
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        match buf {
+            U8(data) => {
+                for chunk in data.chunks(self.chunk_size) {
+                    self.interface.send_data(U8(chunk)).await?;
+                }
+
+                Ok(())
+            }
+            buf => self.interface.send_data(buf).await,
+        }
+    }
+}
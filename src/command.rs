@@ -2,6 +2,7 @@
 
 // Shamefully taken from https://github.com/EdgewaterDevelopment/rust-sh1106
 
+use crate::Error;
 #[cfg(feature = "async")]
 use display_interface::AsyncWriteOnlyDataCommand;
 use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
@@ -9,6 +10,7 @@ use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
 /// SH1106 Commands
 #[maybe_async_cfg::maybe(sync(keep_self), async(feature = "async"))]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Command {
     /// 81h Set contrast. Higher number is higher contrast. Default = 0x80
     Contrast(u8),
@@ -31,6 +33,7 @@ pub enum Command {
     UpperColStart(u8),
     /// Set the column start address register
     /// Combines LowerColStart and UpperColStart
+    /// The address isn't checked, use [`Command::col_start`] to reject columns past the RAM
     ColStart(u8),
     ///  B0h-B7h Set Page Address
     ///
@@ -85,52 +88,152 @@ pub enum Command {
     )
 )]
 impl Command {
+    /// Last column of the 132 columns of SH1106 display RAM
+    pub const MAX_COLUMN: u8 = 131;
+
+    /// Create a [`ColStart`](Command::ColStart) command, or [`OutOfRange`] if `column` is past
+    /// [`MAX_COLUMN`](Command::MAX_COLUMN).
+    ///
+    /// ```rust
+    /// use sh1106::command::{Command, OutOfRange};
+    ///
+    /// assert!(matches!(Command::col_start(0), Ok(Command::ColStart(0))));
+    /// assert!(matches!(Command::col_start(2), Ok(Command::ColStart(2))));
+    /// assert!(matches!(Command::col_start(127), Ok(Command::ColStart(127))));
+    /// assert!(matches!(Command::col_start(131), Ok(Command::ColStart(131))));
+    /// assert!(matches!(Command::col_start(132), Err(OutOfRange)));
+    /// ```
+    pub fn col_start(column: u8) -> Result<Self, OutOfRange> {
+        match column {
+            0..=Self::MAX_COLUMN => Ok(Command::ColStart(column)),
+            _ => Err(OutOfRange),
+        }
+    }
+
+    /// Create a [`ColStart`](Command::ColStart) command for `column` of the visible area of a
+    /// panel starting at display RAM column `offset`, such as the column
+    /// offset set with [`with_column_offset`](crate::Sh1106::with_column_offset). Columns which end up past
+    /// [`MAX_COLUMN`](Command::MAX_COLUMN) return [`OutOfRange`] instead of wrapping.
+    ///
+    /// ```rust
+    /// use sh1106::command::{Command, OutOfRange};
+    ///
+    /// // The 128x64 panels start at display RAM column 2
+    /// assert!(matches!(Command::col_start_with_offset(0, 2), Ok(Command::ColStart(2))));
+    /// assert!(matches!(Command::col_start_with_offset(127, 2), Ok(Command::ColStart(129))));
+    /// assert!(matches!(Command::col_start_with_offset(129, 2), Ok(Command::ColStart(131))));
+    /// assert!(matches!(Command::col_start_with_offset(130, 2), Err(OutOfRange)));
+    /// assert!(matches!(Command::col_start_with_offset(255, 2), Err(OutOfRange)));
+    /// ```
+    pub fn col_start_with_offset(column: u8, offset: u8) -> Result<Self, OutOfRange> {
+        Self::col_start(column.checked_add(offset).ok_or(OutOfRange)?)
+    }
+
     /// Send command to SH1106
     pub async fn send<DI>(self, iface: &mut DI) -> Result<(), DisplayError>
     where
         DI: WriteOnlyDataCommand,
     {
-        match self {
-            Command::Contrast(val) => Self::send_commands(iface, &[0x81, val]).await,
-            Command::AllOn(on) => Self::send_commands(iface, &[0xA4 | (on as u8)]).await,
-            Command::Invert(inv) => Self::send_commands(iface, &[0xA6 | (inv as u8)]).await,
-            Command::DisplayOn(on) => Self::send_commands(iface, &[0xAE | (on as u8)]).await,
-            Command::LowerColStart(addr) => Self::send_commands(iface, &[0xF & addr]).await,
-            Command::UpperColStart(addr) => {
-                Self::send_commands(iface, &[0x10 | (0xF & addr)]).await
-            }
-            Command::ColStart(addr) => {
-                Self::send_commands(iface, &[0xF & addr, 0x10 | (0xF & (addr >> 4))]).await
-            }
-            Command::PageStart(page) => Self::send_commands(iface, &[0xB0 | (page as u8)]).await,
-            Command::StartLine(line) => Self::send_commands(iface, &[0x40 | (0x3F & line)]).await,
-            Command::SegmentRemap(remap) => {
-                Self::send_commands(iface, &[0xA0 | (remap as u8)]).await
-            }
-            Command::Multiplex(ratio) => Self::send_commands(iface, &[0xA8, ratio]).await,
-            Command::ReverseComDir(rev) => {
-                Self::send_commands(iface, &[0xC0 | ((rev as u8) << 3)]).await
-            }
-            Command::DisplayOffset(offset) => Self::send_commands(iface, &[0xD3, offset]).await,
-            Command::ComPinConfig(alt) => {
-                Self::send_commands(iface, &[0xDA, 0x2 | ((alt as u8) << 4)]).await
+        let (bytes, len) = self.encode();
+
+        Self::send_commands(iface, &bytes[..len]).await
+    }
+
+    /// Send several commands to the SH1106 in as few transfers as possible.
+    ///
+    /// The bytes sent are exactly the same as calling [`send`](Self::send) for each command in
+    /// turn, but are packed into transfers of up to 7 bytes, the most `display-interface-i2c` can
+    /// send in one command transfer. Over I2C this saves the start condition, address and control
+    /// byte for most commands.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// use sh1106::command::{Command, Page};
+    ///
+    /// let commands = [
+    ///     Command::DisplayOn(false),
+    ///     Command::Multiplex(63),
+    ///     Command::ColStart(0x12),
+    ///     Command::PageStart(Page::Page3),
+    ///     Command::DisplayOn(true),
+    /// ];
+    ///
+    /// let mut batched = MockInterface::new();
+    /// Command::send_batch(&mut batched, &commands).unwrap();
+    ///
+    /// let mut individual = MockInterface::new();
+    /// for command in commands {
+    ///     command.send(&mut individual).unwrap();
+    /// }
+    ///
+    /// assert_eq!(batched.commands(), individual.commands());
+    /// assert_eq!(
+    ///     batched.commands(),
+    ///     &[0xAE, 0xA8, 0x3F, 0x02, 0x11, 0xB3, 0xAF]
+    /// );
+    /// ```
+    pub async fn send_batch<DI>(iface: &mut DI, commands: &[Command]) -> Result<(), DisplayError>
+    where
+        DI: WriteOnlyDataCommand,
+    {
+        let mut buf = [0; 7];
+        let mut len = 0;
+
+        for command in commands {
+            let (bytes, n) = command.encode();
+
+            if len + n > buf.len() {
+                Self::send_commands(iface, &buf[..len]).await?;
+                len = 0;
             }
+
+            buf[len..len + n].copy_from_slice(&bytes[..n]);
+            len += n;
+        }
+
+        if len > 0 {
+            Self::send_commands(iface, &buf[..len]).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the bytes sent for this command, and how many of them are used
+    fn encode(self) -> ([u8; 2], usize) {
+        fn encoded(bytes: &[u8]) -> ([u8; 2], usize) {
+            let mut buf = [0; 2];
+            buf[..bytes.len()].copy_from_slice(bytes);
+
+            (buf, bytes.len())
+        }
+
+        match self {
+            Command::Contrast(val) => encoded(&[0x81, val]),
+            Command::AllOn(on) => encoded(&[0xA4 | (on as u8)]),
+            Command::Invert(inv) => encoded(&[0xA6 | (inv as u8)]),
+            Command::DisplayOn(on) => encoded(&[0xAE | (on as u8)]),
+            Command::LowerColStart(addr) => encoded(&[0xF & addr]),
+            Command::UpperColStart(addr) => encoded(&[0x10 | (0xF & addr)]),
+            Command::ColStart(addr) => encoded(&[0xF & addr, 0x10 | (0xF & (addr >> 4))]),
+            Command::PageStart(page) => encoded(&[0xB0 | (page as u8)]),
+            Command::StartLine(line) => encoded(&[0x40 | (0x3F & line)]),
+            Command::SegmentRemap(remap) => encoded(&[0xA0 | (remap as u8)]),
+            Command::Multiplex(ratio) => encoded(&[0xA8, ratio]),
+            Command::ReverseComDir(rev) => encoded(&[0xC0 | ((rev as u8) << 3)]),
+            Command::DisplayOffset(offset) => encoded(&[0xD3, offset]),
+            Command::ComPinConfig(alt) => encoded(&[0xDA, 0x2 | ((alt as u8) << 4)]),
             Command::DisplayClockDiv(fosc, div) => {
-                Self::send_commands(iface, &[0xD5, ((0xF & fosc) << 4) | (0xF & div)]).await
+                encoded(&[0xD5, ((0xF & fosc) << 4) | (0xF & div)])
             }
             Command::PreChargePeriod(phase1, phase2) => {
-                Self::send_commands(iface, &[0xD9, ((0xF & phase2) << 4) | (0xF & phase1)]).await
-            }
-            Command::VcomhDeselect(level) => {
-                Self::send_commands(iface, &[0xDB, (level as u8) << 4]).await
+                encoded(&[0xD9, ((0xF & phase2) << 4) | (0xF & phase1)])
             }
-            Command::Noop => Self::send_commands(iface, &[0xE3]).await,
-            Command::ChargePump(en) => Self::send_commands(iface, &[0xAD, 0x8A | (en as u8)]).await,
-            Command::SetPumpVoltage(voltage) => {
-                Self::send_commands(iface, &[0x30 | (voltage as u8)]).await
-            }
-            Command::ReadModifyWriteStart => Self::send_commands(iface, &[0xE0]).await,
-            Command::ReadModifyWriteEnd => Self::send_commands(iface, &[0xEE]).await,
+            Command::VcomhDeselect(level) => encoded(&[0xDB, (level as u8) << 4]),
+            Command::Noop => encoded(&[0xE3]),
+            Command::ChargePump(en) => encoded(&[0xAD, 0x8A | (en as u8)]),
+            Command::SetPumpVoltage(voltage) => encoded(&[0x30 | (voltage as u8)]),
+            Command::ReadModifyWriteStart => encoded(&[0xE0]),
+            Command::ReadModifyWriteEnd => encoded(&[0xEE]),
         }
     }
 
@@ -143,7 +246,25 @@ impl Command {
 }
 
 /// Display page
-#[derive(Debug, Clone, Copy)]
+///
+/// Each page is a row of the display RAM 8 pixels high. Pages are created from a pixel row, which
+/// is rounded down to the page containing it.
+///
+/// ```rust
+/// use sh1106::command::{OutOfRange, Page};
+///
+/// assert_eq!(Page::from_row(55), Ok(Page::Page6));
+/// assert_eq!(Page::from_row(56), Ok(Page::Page7));
+/// assert_eq!(Page::from_row(63), Ok(Page::Page7));
+/// assert_eq!(Page::from_row(64), Err(OutOfRange));
+/// assert_eq!(Page::from_row(255), Err(OutOfRange));
+///
+/// // The infallible conversion clamps to the last page instead
+/// assert_eq!(Page::from(64), Page::Page7);
+/// assert_eq!(Page::from(255), Page::Page7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Page {
     /// Page 0
     Page0 = 0b0000,
@@ -163,25 +284,72 @@ pub enum Page {
     Page7 = 0b0111,
 }
 
+/// Error returned when a value is outside the range of a register, or a pixel row is past the last
+/// page of the display RAM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange;
+
+impl Page {
+    /// Get the page containing the given pixel row, or [`OutOfRange`] if the row is past the
+    /// last page.
+    ///
+    /// This is the fallible counterpart of the `From<u8>` conversion, which clamps to
+    /// [`Page::Page7`]. `TryFrom<u8>` can't be implemented alongside `From<u8>`.
+    pub fn from_row(row: u8) -> Result<Page, OutOfRange> {
+        match row / 8 {
+            0 => Ok(Page::Page0),
+            1 => Ok(Page::Page1),
+            2 => Ok(Page::Page2),
+            3 => Ok(Page::Page3),
+            4 => Ok(Page::Page4),
+            5 => Ok(Page::Page5),
+            6 => Ok(Page::Page6),
+            7 => Ok(Page::Page7),
+            _ => Err(OutOfRange),
+        }
+    }
+}
+
 impl From<u8> for Page {
     fn from(val: u8) -> Page {
-        match val / 8 {
-            0 => Page::Page0,
-            1 => Page::Page1,
-            2 => Page::Page2,
-            3 => Page::Page3,
-            4 => Page::Page4,
-            5 => Page::Page5,
-            6 => Page::Page6,
-            7 => Page::Page7,
-            _ => unreachable!(),
-        }
+        Page::from_row(val).unwrap_or(Page::Page7)
+    }
+}
+
+impl From<OutOfRange> for DisplayError {
+    fn from(_: OutOfRange) -> Self {
+        DisplayError::OutOfBoundsError
+    }
+}
+
+impl From<OutOfRange> for Error {
+    fn from(_: OutOfRange) -> Self {
+        Error::InvalidConfig("row is past the last page of the display RAM")
     }
 }
 
 /// VCOM voltage levels based on the formula:
 /// VCOM = (0.430 + A\[7:0\] * 0.006415) * VREF
-#[derive(Debug, Clone, Copy, Default)]
+///
+/// Each variant holds the value of A in the formula, from `0x00` to `0x40`. Like the driver this
+/// crate is based on, [`Command::VcomhDeselect`] sends the value shifted left by 4 bits, keeping
+/// only the low 8 bits, so the byte sent after `0xDB` is not the value itself. For example
+/// [`V0455`](VcomhLevel::V0455) sends `0x40` and the default [`V0769`](VcomhLevel::V0769) sends
+/// `0x50`, both of which the datasheet treats as 1 * VREF.
+///
+/// ```rust
+/// # use sh1106::test_helpers::MockInterface;
+/// use sh1106::command::{Command, VcomhLevel};
+///
+/// let mut interface = MockInterface::new();
+///
+/// Command::VcomhDeselect(VcomhLevel::V0455).send(&mut interface).unwrap();
+/// Command::VcomhDeselect(VcomhLevel::V0769).send(&mut interface).unwrap();
+///
+/// assert_eq!(VcomhLevel::V0455.as_byte(), 0x04);
+/// assert_eq!(interface.commands(), &[0xDB, 0x40, 0xDB, 0x50]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum VcomhLevel {
     /// 0.430 * VREF
@@ -317,7 +485,160 @@ pub enum VcomhLevel {
     V1000 = 0x40,
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+impl VcomhLevel {
+    /// All levels, indexed by their value of A
+    const LEVELS: [VcomhLevel; 65] = [
+        VcomhLevel::V0430,
+        VcomhLevel::V0436,
+        VcomhLevel::V0442,
+        VcomhLevel::V0449,
+        VcomhLevel::V0455,
+        VcomhLevel::V0462,
+        VcomhLevel::V0468,
+        VcomhLevel::V0474,
+        VcomhLevel::V0481,
+        VcomhLevel::V0487,
+        VcomhLevel::V0494,
+        VcomhLevel::V0500,
+        VcomhLevel::V0506,
+        VcomhLevel::V0513,
+        VcomhLevel::V0519,
+        VcomhLevel::V0526,
+        VcomhLevel::V0532,
+        VcomhLevel::V0539,
+        VcomhLevel::V0545,
+        VcomhLevel::V0551,
+        VcomhLevel::V0558,
+        VcomhLevel::V0564,
+        VcomhLevel::V0571,
+        VcomhLevel::V0577,
+        VcomhLevel::V0583,
+        VcomhLevel::V0590,
+        VcomhLevel::V0596,
+        VcomhLevel::V0603,
+        VcomhLevel::V0609,
+        VcomhLevel::V0616,
+        VcomhLevel::V0622,
+        VcomhLevel::V0628,
+        VcomhLevel::V0635,
+        VcomhLevel::V0641,
+        VcomhLevel::V0648,
+        VcomhLevel::V0654,
+        VcomhLevel::V0660,
+        VcomhLevel::V0667,
+        VcomhLevel::V0673,
+        VcomhLevel::V0680,
+        VcomhLevel::V0686,
+        VcomhLevel::V0693,
+        VcomhLevel::V0699,
+        VcomhLevel::V0705,
+        VcomhLevel::V0712,
+        VcomhLevel::V0718,
+        VcomhLevel::V0725,
+        VcomhLevel::V0731,
+        VcomhLevel::V0737,
+        VcomhLevel::V0744,
+        VcomhLevel::V0750,
+        VcomhLevel::V0757,
+        VcomhLevel::V0763,
+        VcomhLevel::V0769,
+        VcomhLevel::V0776,
+        VcomhLevel::V0782,
+        VcomhLevel::V0789,
+        VcomhLevel::V0795,
+        VcomhLevel::V0802,
+        VcomhLevel::V0808,
+        VcomhLevel::V0814,
+        VcomhLevel::V0821,
+        VcomhLevel::V0827,
+        VcomhLevel::V0834,
+        VcomhLevel::V1000,
+    ];
+
+    /// Get the level closest to the given fraction of VREF.
+    ///
+    /// Ratios outside of the supported range are clamped to the lowest or highest level.
+    ///
+    /// ```rust
+    /// use sh1106::command::VcomhLevel;
+    ///
+    /// assert_eq!(VcomhLevel::from_ratio(0.0), VcomhLevel::V0430);
+    /// assert_eq!(VcomhLevel::from_ratio(0.430), VcomhLevel::V0430);
+    /// assert_eq!(VcomhLevel::from_ratio(0.77), VcomhLevel::V0769);
+    /// assert_eq!(VcomhLevel::from_ratio(0.834145), VcomhLevel::V0834);
+    /// assert_eq!(VcomhLevel::from_ratio(0.9), VcomhLevel::V0834);
+    /// assert_eq!(VcomhLevel::from_ratio(1.0), VcomhLevel::V1000);
+    /// assert_eq!(VcomhLevel::from_ratio(2.0), VcomhLevel::V1000);
+    /// ```
+    pub fn from_ratio(ratio: f32) -> VcomhLevel {
+        let highest = VcomhLevel::V0834.to_ratio();
+
+        if ratio >= (highest + VcomhLevel::V1000.to_ratio()) / 2.0 {
+            return VcomhLevel::V1000;
+        }
+
+        let steps = (ratio - 0.430) / 0.006415;
+
+        // Rounds to the nearest step. Negative steps saturate to 0 when cast.
+        let index = ((steps + 0.5) as u8).min(VcomhLevel::V0834 as u8);
+
+        Self::LEVELS[index as usize]
+    }
+
+    /// Get the level as a fraction of VREF.
+    ///
+    /// ```rust
+    /// use sh1106::command::VcomhLevel;
+    ///
+    /// assert_eq!(VcomhLevel::V0430.to_ratio(), 0.430);
+    /// assert_eq!(VcomhLevel::V1000.to_ratio(), 1.0);
+    /// ```
+    pub fn to_ratio(self) -> f32 {
+        match self {
+            VcomhLevel::V1000 => 1.0,
+            level => 0.430 + level as u8 as f32 * 0.006415,
+        }
+    }
+
+    /// Get the value of A in the formula for the level, from `0x00` to `0x40`.
+    ///
+    /// This is not the byte sent by [`Command::VcomhDeselect`], see [`VcomhLevel`].
+    pub fn as_byte(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Get the level for a value of A in the formula from `0x00` to `0x40`, as returned by
+/// [`as_byte`](VcomhLevel::as_byte).
+///
+/// ```rust
+/// use sh1106::command::{OutOfRange, VcomhLevel};
+///
+/// assert_eq!(VcomhLevel::try_from(0x35), Ok(VcomhLevel::V0769));
+/// assert_eq!(VcomhLevel::try_from(0x40), Ok(VcomhLevel::V1000));
+/// assert_eq!(VcomhLevel::try_from(0x41), Err(OutOfRange));
+///
+/// let level = VcomhLevel::V0776;
+/// assert_eq!(VcomhLevel::try_from(level.as_byte()), Ok(level));
+/// ```
+impl TryFrom<u8> for VcomhLevel {
+    type Error = OutOfRange;
+
+    fn try_from(val: u8) -> Result<VcomhLevel, OutOfRange> {
+        Self::LEVELS.get(val as usize).copied().ok_or(OutOfRange)
+    }
+}
+
+// Formatted as the value of A, so the 65 variant names don't each need their own string
+#[cfg(feature = "defmt")]
+impl defmt::Format for VcomhLevel {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "VcomhLevel({=u8:#04x})", self.as_byte())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[allow(dead_code)]
 /// Pump output voltage (VPP)
 pub enum PumpVoltage {
@@ -331,3 +652,36 @@ pub enum PumpVoltage {
     /// 9V
     V90 = 3,
 }
+
+impl PumpVoltage {
+    /// Get the register value of the pump voltage.
+    pub fn as_byte(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Get the pump voltage for a register value from 0 to 3.
+///
+/// ```rust
+/// use sh1106::command::{OutOfRange, PumpVoltage};
+///
+/// assert_eq!(PumpVoltage::try_from(0), Ok(PumpVoltage::V64));
+/// assert_eq!(PumpVoltage::try_from(3), Ok(PumpVoltage::V90));
+/// assert_eq!(PumpVoltage::try_from(4), Err(OutOfRange));
+///
+/// let voltage = PumpVoltage::V74;
+/// assert_eq!(PumpVoltage::try_from(voltage.as_byte()), Ok(voltage));
+/// ```
+impl TryFrom<u8> for PumpVoltage {
+    type Error = OutOfRange;
+
+    fn try_from(val: u8) -> Result<PumpVoltage, OutOfRange> {
+        match val {
+            0 => Ok(PumpVoltage::V64),
+            1 => Ok(PumpVoltage::V74),
+            2 => Ok(PumpVoltage::V80),
+            3 => Ok(PumpVoltage::V90),
+            _ => Err(OutOfRange),
+        }
+    }
+}
@@ -0,0 +1,344 @@
+//! Display initialisation settings
+
+use crate::{
+    brightness::Brightness,
+    command::{PumpVoltage, VcomhLevel},
+    rotation::DisplayRotation,
+};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+/// Maximum number of command bytes in an [`InitCommands`] sequence
+const INIT_COMMANDS_CAPACITY: usize = 64;
+
+/// Settings sent to the display by [`Sh1106::init_with_config`](crate::Sh1106::init_with_config).
+///
+/// The defaults match [`init_default`](crate::Sh1106::init_default), apart from the contrast
+/// which `init_default` takes from the display size, and work for most panels.
+/// Panels which flicker or show faint ghosting can often be fixed by adjusting the clock divider
+/// or pre-charge period, see the SH1106 datasheet and the panel vendor's recommended init
+/// sequence.
+///
+/// ```rust
+/// use sh1106::{command::PumpVoltage, prelude::*};
+///
+/// let config = InitConfig {
+///     clock_div: (0x8, 0x0),
+///     precharge: Some((0x2, 0x2)),
+///     pump_voltage: Some(PumpVoltage::V90),
+///     ..InitConfig::default()
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InitConfig {
+    /// Oscillator frequency and clock divide ratio, see
+    /// [`Command::DisplayClockDiv`](crate::command::Command::DisplayClockDiv).
+    pub clock_div: (u8, u8),
+    /// Pre-charge and discharge periods, see
+    /// [`Command::PreChargePeriod`](crate::command::Command::PreChargePeriod). `None` uses the
+    /// pre-charge period of `brightness`, and `Some` replaces it.
+    pub precharge: Option<(u8, u8)>,
+    /// Use the alternative COM pin configuration, see
+    /// [`Command::ComPinConfig`](crate::command::Command::ComPinConfig). `None` uses the
+    /// configuration of the [`DisplaySize`](crate::size::DisplaySize), and `Some` is sent
+    /// instead of it.
+    ///
+    /// Each of these settings is sent once, whether it is overridden or not:
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let display = Sh1106::new(StubInterface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// let commands = display
+    ///     .init_commands(InitConfig {
+    ///         precharge: Some((0x1, 0xF)),
+    ///         com_pin_alt: Some(false),
+    ///         ..InitConfig::default()
+    ///     })
+    ///     .unwrap();
+    /// let commands = commands.as_bytes();
+    ///
+    /// assert_eq!(commands.iter().filter(|byte| **byte == 0xD9).count(), 1);
+    /// assert!(commands.windows(2).any(|c| c == [0xD9, 0xF1]));
+    /// assert_eq!(commands.iter().filter(|byte| **byte == 0xDA).count(), 1);
+    /// assert!(commands.windows(2).any(|c| c == [0xDA, 0x02]));
+    /// ```
+    pub com_pin_alt: Option<bool>,
+    /// Charge pump output voltage. `None` leaves the display at its reset value of
+    /// [`PumpVoltage::V80`].
+    pub pump_voltage: Option<PumpVoltage>,
+    /// Whether the panel voltage VPP is generated by the internal charge pump of the SH1106.
+    ///
+    /// This is `true` for most modules, which are powered from a single 3.3V or 5V supply. Some
+    /// modules instead supply VPP externally, usually from a boost converter on the module (a
+    /// small IC next to an inductor) or through a separate 7V to 12V VPP pin, see the schematic of
+    /// the module. Set this to `false` for those. The charge pump is on after a reset, so it is
+    /// then turned off explicitly, and `pump_voltage` isn't sent.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{command::PumpVoltage, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display
+    ///     .init_with_config(InitConfig {
+    ///         internal_charge_pump: false,
+    ///         pump_voltage: Some(PumpVoltage::V90),
+    ///         ..InitConfig::default()
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let commands = display.release().commands().to_vec();
+    ///
+    /// // The charge pump is turned off, and neither turned on nor set to a voltage
+    /// assert!(commands.windows(2).any(|command| command == [0xAD, 0x8A]));
+    /// assert!(!commands.windows(2).any(|command| command == [0xAD, 0x8B]));
+    /// assert!(!commands.contains(&0x33));
+    /// ```
+    pub internal_charge_pump: bool,
+    /// Initial brightness.
+    pub brightness: Brightness,
+    /// VCOM deselect level.
+    pub vcomh: VcomhLevel,
+}
+
+impl Default for InitConfig {
+    fn default() -> Self {
+        Self {
+            // 0x5 is default for SH1106
+            clock_div: (0x5, 0x0),
+            precharge: None,
+            com_pin_alt: None,
+            pump_voltage: None,
+            internal_charge_pump: true,
+            brightness: Brightness::default(),
+            vcomh: VcomhLevel::default(),
+        }
+    }
+}
+
+/// Named initialisation settings for specific panels, set with
+/// [`Builder::profile`](crate::Builder::profile) or
+/// [`Sh1106::with_profile`](crate::Sh1106::with_profile).
+///
+/// The presets bundle the clock, pre-charge, contrast and VCOMH settings of the init sequences
+/// the panel vendors publish, so a panel out of the box shows a good image without tuning each
+/// setting. [`init`](crate::mode::DisplayConfig::init) and
+/// [`init_default`](crate::Sh1106::init_default) send the profile instead of the defaults of the
+/// display size.
+///
+/// ```rust
+/// # use sh1106::test_helpers::MockInterface;
+/// # let interface = MockInterface::new();
+/// use sh1106::{prelude::*, Builder};
+///
+/// let mut display = Builder::new()
+///     .profile(PanelProfile::Crius128x64)
+///     .connect(interface)
+///     .into_buffered_graphics_mode();
+/// display.init().unwrap();
+///
+/// let commands = display.release().commands().to_vec();
+///
+/// assert!(commands.windows(2).any(|c| c == [0xD5, 0x80]));
+/// assert!(commands.windows(2).any(|c| c == [0xD9, 0xF1]));
+/// assert!(commands.windows(2).any(|c| c == [0x81, 0xCF]));
+/// assert!(commands.windows(2).any(|c| c == [0xDB, 0x40]));
+/// ```
+///
+/// The whole sequence sent for a preset:
+///
+/// ```rust
+/// # use sh1106::test_helpers::StubInterface;
+/// use sh1106::{prelude::*, Sh1106};
+///
+/// let display = Sh1106::new(StubInterface, DisplaySize128x64, DisplayRotation::Rotate0);
+/// let commands = display
+///     .init_commands(PanelProfile::Crius128x64.init_config())
+///     .unwrap();
+///
+/// #[rustfmt::skip]
+/// assert_eq!(
+///     commands.as_bytes(),
+///     &[
+///         0xAE,
+///         0xD5, 0x80, // Faster clock
+///         0xA8, 0x3F,
+///         0xD3, 0x00,
+///         0x40,
+///         0xAD, 0x8B,
+///         0xDA, 0x12,
+///         0xA1,
+///         0xC8,
+///         0xD9, 0xF1, // Long discharge period
+///         0x81, 0xCF, // Contrast
+///         0xDB, 0x40, // VCOMH deselect level
+///         0xA4,
+///         0xA6,
+///         0xAF,
+///     ]
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PanelProfile {
+    /// The 0.96" and 1.3" 128x64 Crius CO-16 modules and their clones, with the faster clock,
+    /// long discharge period, contrast of 0xCF and VCOMH level of 0x40 from the vendor's
+    /// Arduino library.
+    Crius128x64,
+    /// The 0.42" 72x40 panels found on ESP32-C3 and similar development boards, with the faster
+    /// clock, pre-charge and discharge periods of 2 and a contrast of 0x7F.
+    Generic72x40,
+    /// Any panel which has to be readable in bright light, with the full contrast, a long
+    /// discharge period and a charge pump voltage of 9V. This wears the panel faster than the
+    /// defaults.
+    HighContrast,
+    /// The given settings.
+    Custom(InitConfig),
+}
+
+impl PanelProfile {
+    /// Get the settings sent to the display for this profile.
+    pub fn init_config(self) -> InitConfig {
+        match self {
+            PanelProfile::Crius128x64 => InitConfig {
+                clock_div: (0x8, 0x0),
+                precharge: Some((0x1, 0xF)),
+                brightness: Brightness::custom(0x1, 0xCF),
+                // Sent as `0xDB 0x40`, the VCOMH byte of the vendor init sequence, as the level
+                // is shifted left by 4 bits (see `VcomhLevel`)
+                vcomh: VcomhLevel::V0455,
+                ..InitConfig::default()
+            },
+            PanelProfile::Generic72x40 => InitConfig {
+                clock_div: (0x8, 0x0),
+                precharge: Some((0x2, 0x2)),
+                brightness: Brightness::custom(0x2, 0x7F),
+                ..InitConfig::default()
+            },
+            PanelProfile::HighContrast => InitConfig {
+                precharge: Some((0x1, 0xF)),
+                pump_voltage: Some(PumpVoltage::V90),
+                brightness: Brightness::BRIGHTEST,
+                ..InitConfig::default()
+            },
+            PanelProfile::Custom(config) => config,
+        }
+    }
+}
+
+impl From<PanelProfile> for InitConfig {
+    fn from(profile: PanelProfile) -> Self {
+        profile.init_config()
+    }
+}
+
+/// Snapshot of the display settings last sent by the driver, returned by
+/// [`Sh1106::config`](crate::Sh1106::config).
+///
+/// The display can't be read back over I2C or 4-wire SPI, so this is what the driver believes
+/// the display is set to, not a read of its registers. Before the display is initialised, it
+/// shows the state the driver will send, and assumes the display is off and not inverted as after
+/// a reset.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DisplayConfigState {
+    /// Contrast, see [`Sh1106::set_contrast`](crate::Sh1106::set_contrast).
+    pub contrast: u8,
+    /// Rotation, see [`Sh1106::set_rotation`](crate::Sh1106::set_rotation).
+    pub rotation: DisplayRotation,
+    /// Whether the display is inverted, see [`Sh1106::set_invert`](crate::Sh1106::set_invert).
+    pub invert: bool,
+    /// Whether the display is on, see [`Sh1106::set_display_on`](crate::Sh1106::set_display_on).
+    pub display_on: bool,
+    /// Display RAM row shown at the top of the display, see
+    /// [`Sh1106::set_start_line`](crate::Sh1106::set_start_line).
+    pub start_line: u8,
+    /// Horizontal mirroring, see [`Sh1106::set_mirror`](crate::Sh1106::set_mirror).
+    pub mirror_horizontal: bool,
+    /// Vertical mirroring, see [`Sh1106::set_mirror`](crate::Sh1106::set_mirror).
+    pub mirror_vertical: bool,
+}
+
+/// The command bytes of an initialisation sequence, returned by
+/// [`Sh1106::init_commands`](crate::Sh1106::init_commands).
+///
+/// These are the bytes [`Sh1106::init_with_config`](crate::Sh1106::init_with_config) sends, in
+/// order, so sending them with `send_commands` on any interface initialises the display in the
+/// same way.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InitCommands {
+    bytes: [u8; INIT_COMMANDS_CAPACITY],
+    len: usize,
+}
+
+impl InitCommands {
+    /// Get the command bytes in the order they are sent.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl AsRef<[u8]> for InitCommands {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Interface recording the command bytes of the initialisation sequence instead of sending them
+pub(crate) struct InitRecorder(InitCommands);
+
+impl InitRecorder {
+    pub(crate) fn new() -> Self {
+        Self(InitCommands {
+            bytes: [0; INIT_COMMANDS_CAPACITY],
+            len: 0,
+        })
+    }
+
+    pub(crate) fn into_commands(self) -> InitCommands {
+        self.0
+    }
+
+    fn record(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        let DataFormat::U8(cmds) = cmds else {
+            return Err(DisplayError::DataFormatNotImplemented);
+        };
+        let commands = &mut self.0;
+        let end = commands.len + cmds.len();
+
+        commands
+            .bytes
+            .get_mut(commands.len..end)
+            .ok_or(DisplayError::OutOfBoundsError)?
+            .copy_from_slice(cmds);
+        commands.len = end;
+
+        Ok(())
+    }
+}
+
+impl WriteOnlyDataCommand for InitRecorder {
+    fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.record(cmds)
+    }
+
+    fn send_data(&mut self, _buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        Err(DisplayError::InvalidFormatError)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncWriteOnlyDataCommand for InitRecorder {
+    async fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.record(cmds)
+    }
+
+    async fn send_data(&mut self, _buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        Err(DisplayError::InvalidFormatError)
+    }
+}
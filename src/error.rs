@@ -1,8 +1,69 @@
-/// Errors in this crate
-#[derive(Debug)]
-pub enum Error<CommE, PinE> {
-    /// Communication error
-    Comm(CommE),
-    /// Pin setting error
-    Pin(PinE),
+//! Driver errors
+
+use display_interface::DisplayError;
+
+/// Errors returned by the driver.
+///
+/// Errors of the display interface are wrapped in a variant telling in which stage of using the
+/// display they happened. For example, a display which fails to [`Init`](Error::Init) usually
+/// isn't wired up correctly, while a [`Flush`](Error::Flush) failing on a display which was
+/// working before points to a glitch on the bus. Errors of the display interface can be converted
+/// into an `Error` with `?`, and the original error is available from
+/// [`display_error`](Error::display_error).
+///
+/// ```rust
+/// # use sh1106::test_helpers::MockInterface;
+/// # let mut interface = MockInterface::new();
+/// use display_interface::DisplayError;
+/// use sh1106::{prelude::*, Error, Sh1106};
+///
+/// interface.fail_data(1);
+///
+/// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// display.init().unwrap();
+/// display.set_pixel(0, 0, true);
+///
+/// let error = display.flush().unwrap_err();
+///
+/// assert_eq!(error, Error::Flush(DisplayError::BusWriteError));
+/// assert_eq!(error.display_error(), Some(&DisplayError::BusWriteError));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The display interface failed while initialising the display
+    Init(DisplayError),
+    /// The display interface failed while flushing the framebuffer to the display
+    Flush(DisplayError),
+    /// The display interface failed while sending any other command or data
+    Command(DisplayError),
+    /// A setting or argument was not valid for the display
+    InvalidConfig(&'static str),
+}
+
+impl Error {
+    /// Get the error of the display interface, or `None` if the error didn't come from the
+    /// display interface.
+    pub fn display_error(&self) -> Option<&DisplayError> {
+        match self {
+            Self::Init(error) | Self::Flush(error) | Self::Command(error) => Some(error),
+            Self::InvalidConfig(_) => None,
+        }
+    }
+
+    /// Re-wrap an error of the display interface in the variant for `stage`, keeping other
+    /// errors unchanged.
+    pub(crate) fn in_stage(self, stage: fn(DisplayError) -> Self) -> Self {
+        match self {
+            Self::Init(error) | Self::Flush(error) | Self::Command(error) => stage(error),
+            error => error,
+        }
+    }
+}
+
+impl From<DisplayError> for Error {
+    fn from(error: DisplayError) -> Self {
+        Self::Command(error)
+    }
 }
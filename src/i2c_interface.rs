@@ -1,8 +1,17 @@
 //! I2C interface factory
 
+use crate::ChunkedInterface;
 use display_interface_i2c::I2CInterface;
 
 /// Helper struct to create preconfigured I2C interfaces for the display.
+///
+/// The I2C address of the SH1106 is selected by its SA0 pin: 0x3C when SA0 is tied low, and 0x3D
+/// when it is tied high. Most modules tie SA0 low, and some have a solder jumper or resistor on
+/// the back to select the alternate address.
+///
+/// The interfaces accept anything implementing the `embedded_hal::i2c::I2c` trait, so the bus can
+/// be shared with other devices by passing a handle such as `embedded_hal_bus::i2c::RefCellDevice`
+/// instead of the bus itself. See the `shared_i2c` example.
 #[derive(Debug, Copy, Clone)]
 pub struct I2CDisplayInterface(());
 
@@ -18,6 +27,8 @@ impl I2CDisplayInterface {
     }
 
     /// Create a new I2C interface with the alternate address 0x3D as specified in the datasheet.
+    ///
+    /// Use this for modules with the SA0 pin tied high.
     pub fn new_alternate_address<I>(i2c: I) -> I2CInterface<I>
     where
         I: embedded_hal::i2c::I2c,
@@ -32,4 +43,31 @@ impl I2CDisplayInterface {
     {
         I2CInterface::new(i2c, address, 0x40)
     }
+
+    /// Create a new I2C interface with the default address 0x3C which passes at most
+    /// `chunk_size` bytes of display data to the interface per transfer.
+    ///
+    /// Without this, a whole page of display data is passed to the interface at once. The
+    /// `display-interface-i2c` interface already writes display data in I2C transactions of 16
+    /// bytes plus the control byte, so only a `chunk_size` below 16 changes what is sent on the bus.
+    /// See [`ChunkedInterface`] for the HALs this is known to help with.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::I2cStub;
+    /// # let i2c = I2cStub;
+    /// use sh1106::{prelude::*, I2CDisplayInterface, Sh1106};
+    ///
+    /// let interface = I2CDisplayInterface::with_chunk_size(i2c, 8);
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.init().unwrap();
+    /// display.flush().unwrap();
+    /// ```
+    pub fn with_chunk_size<I>(i2c: I, chunk_size: usize) -> ChunkedInterface<I2CInterface<I>>
+    where
+        I: embedded_hal::i2c::I2c,
+    {
+        ChunkedInterface::new(Self::new(i2c), chunk_size)
+    }
 }
@@ -11,6 +11,8 @@
 //!   [embedded-graphics](https://docs.rs/embedded-graphics).
 //! - [`TerminalMode`] - A bufferless mode supporting drawing text to the display, as well as
 //!   setting cursor positions like a simple terminal.
+//! - [`RawMode`] - A bufferless mode for manually addressing pages and columns of the display RAM.
+//! - [`ConsoleMode`] - A bufferless, append-only text log which scrolls older lines up.
 //!
 //! # Examples
 //!
@@ -94,6 +96,8 @@
 //! [featureset]: https://github.com/rust-embedded-community/embedded-graphics#features
 //! [`BufferedGraphicsMode`]: crate::mode::BufferedGraphicsMode
 //! [`TerminalMode`]: crate::mode::TerminalMode
+//! [`RawMode`]: crate::mode::RawMode
+//! [`ConsoleMode`]: crate::mode::ConsoleMode
 
 #![no_std]
 #![deny(missing_debug_implementations)]
@@ -109,40 +113,108 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![allow(async_fn_in_trait)]
 
+#[cfg(any(test, feature = "mock"))]
+extern crate alloc;
+
+pub mod addressing;
+pub mod animation;
 mod brightness;
+mod builder;
+mod chunked_interface;
 pub mod command;
+mod config;
 mod error;
 mod i2c_interface;
 pub mod mode;
+#[cfg(feature = "parallel")]
+mod parallel_interface;
+pub mod power;
 pub mod prelude;
+mod rmw_interface;
 pub mod rotation;
 pub mod size;
-#[doc(hidden)]
+mod spi_interface;
+#[cfg(any(test, feature = "mock"))]
 pub mod test_helpers;
 
-use core::convert::Infallible;
-
+pub use crate::chunked_interface::ChunkedInterface;
+pub use crate::error::Error;
 pub use crate::i2c_interface::I2CDisplayInterface;
 use crate::mode::BasicMode;
+#[cfg(feature = "parallel")]
+pub use crate::parallel_interface::ParallelInterface;
+pub use crate::rmw_interface::{DisplayStatus, ReadableInterface, RmwInterface};
+#[cfg(feature = "async")]
+pub use crate::rmw_interface::{ReadableInterfaceAsync, RmwInterfaceAsync};
+pub use crate::spi_interface::{SPIBusInterface, SPIDisplayInterface, SPIInterface3Wire};
+use addressing::AddressingStrategy;
 use brightness::Brightness;
+pub use builder::Builder;
+#[cfg(feature = "async")]
+pub use builder::BuilderAsync;
 #[cfg(feature = "async")]
 use command::CommandAsync;
-use command::{Command, VcomhLevel};
+use command::{Command, Page};
+use config::{DisplayConfigState, InitCommands, InitConfig, InitRecorder, PanelProfile};
 #[cfg(feature = "async")]
 use display_interface::AsyncWriteOnlyDataCommand;
 use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
 use embedded_hal::{delay::DelayNs, digital::OutputPin};
 #[cfg(feature = "async")]
 use embedded_hal_async::delay::DelayNs as DelayNsAsync;
-use error::Error;
-use mode::{BufferedGraphicsMode, TerminalMode};
+use mode::{
+    BufferedGraphicsMode, BufferedGraphicsModeRef, ConsoleMode, DiffShadow, FlushOrder, FlushStats,
+    RawMode, TerminalMode,
+};
 #[cfg(feature = "async")]
-use mode::{BufferedGraphicsModeAsync, TerminalModeAsync};
+use mode::{
+    BufferedGraphicsModeAsync, BufferedGraphicsModeRefAsync, ConsoleModeAsync, RawModeAsync,
+    TerminalModeAsync,
+};
 use rotation::DisplayRotation;
 use size::DisplaySize;
 #[cfg(feature = "async")]
 use size::DisplaySizeAsync;
 
+/// Delay between the steps of [`Sh1106::fade_to`] in milliseconds.
+const FADE_STEP_DELAY_MS: u32 = 4;
+
+/// Let other tasks run between the phases of a long command sequence. The blocking driver has no
+/// other tasks to yield to.
+fn yield_now() {}
+
+/// Let other tasks run between the phases of a long command sequence, by returning to the
+/// executor once.
+#[cfg(feature = "async")]
+async fn yield_now_async() {
+    let mut yielded = false;
+
+    core::future::poll_fn(|cx| {
+        if yielded {
+            core::task::Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+
+            core::task::Poll::Pending
+        }
+    })
+    .await
+}
+
+// The driver is `Send` and `Sync` whenever its interface is, so it can be shared in a `Mutex` or
+// moved into an RTIC resource
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    #[allow(dead_code)]
+    const fn driver_is_send_sync<DI: Send + Sync, SIZE: Send + Sync, MODE: Send + Sync>() {
+        assert_send_sync::<Sh1106<DI, SIZE, MODE>>();
+        #[cfg(feature = "async")]
+        assert_send_sync::<Sh1106Async<DI, SIZE, MODE>>();
+    }
+};
+
 /// SH1106 driver.
 ///
 /// Note that some methods are only available when the display is configured in a certain [`mode`].
@@ -153,11 +225,34 @@ pub struct Sh1106<DI, SIZE, MODE> {
     mode: MODE,
     size: SIZE,
     rotation: DisplayRotation,
+    column_offset: u8,
+    /// How the display RAM address is set when flushing
+    addressing: &'static dyn AddressingStrategy,
+    /// Settings sent by `init_default`, or `None` for the defaults of the display size
+    profile: Option<InitConfig>,
+    /// Horizontal and vertical mirroring, applied on top of the rotation
+    mirror: (bool, bool),
+    /// Last contrast sent to the display
+    contrast: u8,
+    /// Display RAM row shown at the top of the display
+    start_line: u8,
+    /// Whether the display was last set to be inverted
+    invert: bool,
+    /// Whether the display was last turned on
+    display_on: bool,
+    /// Number of rows of the display in use, see `set_active_area`
+    height: u8,
 }
 
 #[maybe_async_cfg::maybe(
     sync(keep_self,),
-    async(feature = "async", idents(DisplaySize(async = "DisplaySizeAsync")))
+    async(
+        feature = "async",
+        idents(
+            Builder(async = "BuilderAsync"),
+            DisplaySize(async = "DisplaySizeAsync"),
+        )
+    )
 )]
 impl<DI, SIZE> Sh1106<DI, SIZE, BasicMode>
 where
@@ -165,13 +260,49 @@ where
 {
     /// Create a basic SH1106 interface.
     ///
-    /// Use the `into_*_mode` methods to enable more functionality.
+    /// Use the `into_*_mode` methods to enable more functionality. This is a shorthand for
+    /// configuring a [`Builder`] with the given size and rotation.
     pub fn new(interface: DI, size: SIZE, rotation: DisplayRotation) -> Self {
+        Builder::new()
+            .size(size)
+            .rotation(rotation)
+            .connect(interface)
+    }
+
+    /// Create a driver for a display which is already initialised, without sending anything to
+    /// it.
+    ///
+    /// After a soft reset where the panel kept its power, for example when a bootloader hands
+    /// over to the application, calling [`init`](mode::DisplayConfig::init) again blanks the
+    /// display for a moment. This constructor skips the initialisation instead, and assumes the
+    /// display was initialised with the default [`InitConfig`] for the given size and rotation:
+    /// turned on, not inverted, with the default contrast and start line. The state returned by
+    /// [`config`](Sh1106::config) is set accordingly.
+    ///
+    /// It is up to you to make sure the display really is initialised, otherwise it stays dark or
+    /// shows garbage. The framebuffer of a buffered mode starts out empty and doesn't know what is
+    /// on the display, so redraw the whole screen before relying on partial flushes.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::attach(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// assert!(display.config().display_on);
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.flush().unwrap();
+    ///
+    /// // Only the address of the changed page, no initialisation commands
+    /// assert_eq!(display.release().commands(), &[0xB0, 0x02, 0x10]);
+    /// ```
+    pub fn attach(interface: DI, size: SIZE, rotation: DisplayRotation) -> Self {
         Self {
-            interface,
-            size,
-            mode: BasicMode,
-            rotation,
+            display_on: true,
+            ..Self::new(interface, size, rotation)
         }
     }
 }
@@ -183,7 +314,10 @@ where
         idents(
             DisplaySize(async = "DisplaySizeAsync"),
             BufferedGraphicsMode(async = "BufferedGraphicsModeAsync"),
+            BufferedGraphicsModeRef(async = "BufferedGraphicsModeRefAsync"),
             TerminalMode(async = "TerminalModeAsync"),
+            RawMode(async = "RawModeAsync"),
+            ConsoleMode(async = "ConsoleModeAsync"),
         )
     )
 )]
@@ -198,6 +332,89 @@ where
             interface: self.interface,
             size: self.size,
             rotation: self.rotation,
+            column_offset: self.column_offset,
+            addressing: self.addressing,
+            profile: self.profile,
+            mirror: self.mirror,
+            contrast: self.contrast,
+            start_line: self.start_line,
+            invert: self.invert,
+            display_on: self.display_on,
+            height: self.height,
+        }
+    }
+
+    /// Override the display RAM column offset of the display size.
+    ///
+    /// The SH1106 has 132 columns of display RAM, but most panels only show 128 or fewer of them.
+    /// The offset is the RAM column shown in the leftmost column of the panel, and defaults to
+    /// [`DisplaySize::OFFSETX`]. Use this for panels which show a different window of the
+    /// display RAM.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .with_column_offset(0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.flush().unwrap();
+    ///
+    /// // Page 0, column 0
+    /// assert_eq!(display.release().commands(), &[0xB0, 0x00, 0x10]);
+    /// ```
+    ///
+    /// The offset plus the display width must fit in the display RAM. Otherwise flushing returns
+    /// [`Error::InvalidConfig`] without sending anything, instead of writing to the wrong columns:
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Error, Sh1106};
+    ///
+    /// // 128 columns from RAM column 5 would end past the 132 columns of the SH1106
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .with_column_offset(5)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// assert!(matches!(display.flush(), Err(Error::InvalidConfig(_))));
+    ///
+    /// # let interface = MockInterface::new();
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .with_column_offset(250)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(127, 0, true);
+    /// assert!(matches!(display.flush(), Err(Error::InvalidConfig(_))));
+    /// assert!(display.release().data().is_empty());
+    /// ```
+    pub fn with_column_offset(self, offset: u8) -> Self {
+        Self {
+            column_offset: offset,
+            ..self
+        }
+    }
+
+    /// Change how the display RAM address is set when flushing.
+    ///
+    /// The default is the [`addressing`](DisplaySize::addressing) of the display size, which is
+    /// [`PageAddressing`](addressing::PageAddressing) as described in the datasheet for the
+    /// SH1106 sizes. See the [`addressing`] module for more information.
+    pub fn with_addressing(self, addressing: &'static dyn AddressingStrategy) -> Self {
+        Self { addressing, ..self }
+    }
+
+    /// Set the panel profile sent by [`init_default`](Sh1106::init_default) and the
+    /// [`init`](mode::DisplayConfig::init) of every mode, instead of the defaults of the display
+    /// size. See [`PanelProfile`] for the presets.
+    pub fn with_profile(self, profile: PanelProfile) -> Self {
+        Self {
+            profile: Some(profile.init_config()),
+            ..self
         }
     }
 
@@ -206,7 +423,97 @@ where
     ///
     /// See [`BufferedGraphicsMode`] for more information.
     pub fn into_buffered_graphics_mode(self) -> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE>> {
-        self.into_mode(BufferedGraphicsMode::new())
+        let rotation = self.rotation;
+
+        self.into_mode(BufferedGraphicsMode::new(rotation))
+    }
+
+    /// Convert the display into a buffered graphics mode which draws into the caller-provided
+    /// `buffer` instead of an array stored in the driver.
+    ///
+    /// This allows placing the framebuffer in a specific memory region, or sharing a statically
+    /// allocated buffer. The buffer must be exactly [`DisplaySize::BUFFER_LEN`] bytes long for
+    /// the display size, otherwise [`Error::InvalidConfig`] is returned. It uses the layout
+    /// described in [`buffer`](Sh1106::buffer).
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut buffer = [0; DisplaySize128x64::BUFFER_LEN];
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode_with_buffer(&mut buffer)
+    ///     .unwrap();
+    ///
+    /// display.set_pixel(1, 0, true);
+    /// drop(display);
+    ///
+    /// assert_eq!(buffer[1], 0x01);
+    ///
+    /// // A buffer for a different display size is rejected
+    /// # let interface = StubInterface;
+    /// let mut buffer = [0; 128 * 32 / 8];
+    ///
+    /// assert!(Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode_with_buffer(&mut buffer)
+    ///     .is_err());
+    /// ```
+    pub fn into_buffered_graphics_mode_with_buffer(
+        self,
+        buffer: &mut [u8],
+    ) -> Result<Sh1106<DI, SIZE, BufferedGraphicsModeRef<'_, SIZE>>, Error> {
+        if buffer.len() != SIZE::BUFFER_LEN {
+            return Err(Error::InvalidConfig(
+                "buffer length doesn't match the display size",
+            ));
+        }
+
+        let rotation = self.rotation;
+
+        Ok(self.into_mode(BufferedGraphicsMode::with_buffer(rotation, buffer)))
+    }
+
+    /// Convert the display into a buffered graphics mode which only sends the bytes that changed
+    /// since the last flush.
+    ///
+    /// This works like [`into_buffered_graphics_mode`](Sh1106::into_buffered_graphics_mode), but
+    /// also keeps a copy of the display RAM, which doubles the memory used by the framebuffer.
+    /// On [`flush`](Sh1106::flush) the changed area of the framebuffer is compared against the
+    /// copy byte by byte, and only the runs of changed columns in each page are sent. A UI which
+    /// redraws everything every frame but only changes a little then only sends those changes.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_diff_mode();
+    ///
+    /// display.clear_pattern(0x0F);
+    /// display.flush().unwrap();
+    ///
+    /// // Redraw the same frame, but with one more pixel on
+    /// display.clear_pattern(0x0F);
+    /// display.set_pixel(20, 12, true);
+    /// display.flush().unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // The first frame is sent in full, then only column 20 of page 1
+    /// assert_eq!(interface.commands().len(), 8 * 3 + 3);
+    /// assert_eq!(&interface.commands()[8 * 3..], &[0xB1, 0x06, 0x11]);
+    /// assert_eq!(interface.data().len(), 1024 + 1);
+    /// assert_eq!(interface.data()[1024], 0b0001_1111);
+    /// ```
+    pub fn into_diff_mode(
+        self,
+    ) -> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, DiffShadow<SIZE::Buffer>>> {
+        let rotation = self.rotation;
+
+        self.into_mode(BufferedGraphicsMode::new(rotation))
     }
 
     /// Convert the display into a text-only, terminal-like mode.
@@ -215,6 +522,20 @@ where
     pub fn into_terminal_mode(self) -> Sh1106<DI, SIZE, TerminalMode> {
         self.into_mode(TerminalMode::new())
     }
+
+    /// Convert the display into a bufferless mode for manual page and column addressing.
+    ///
+    /// See [`RawMode`] for more information.
+    pub fn into_raw_mode(self) -> Sh1106<DI, SIZE, RawMode> {
+        self.into_mode(RawMode::new(SIZE::WIDTH, SIZE::HEIGHT))
+    }
+
+    /// Convert the display into a bufferless, append-only text log.
+    ///
+    /// See [`ConsoleMode`] for more information.
+    pub fn into_console_mode(self) -> Sh1106<DI, SIZE, ConsoleMode> {
+        self.into_mode(ConsoleMode::new())
+    }
 }
 
 #[maybe_async_cfg::maybe(
@@ -225,6 +546,8 @@ where
             Command(async = "CommandAsync"),
             DisplaySize(async = "DisplaySizeAsync"),
             WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            DelayNs(async = "DelayNsAsync"),
+            yield_now(async = "yield_now_async"),
         )
     )
 )]
@@ -234,35 +557,259 @@ where
     SIZE: DisplaySize,
 {
     /// Initialise the display
-    pub async fn init_default(&mut self) -> Result<(), DisplayError> {
-        let rotation = self.rotation;
+    ///
+    /// This only sends the configuration commands. The display RAM keeps whatever it contained
+    /// before, including the columns outside the visible area of the panel. The settings are
+    /// those of the [`PanelProfile`] set with [`with_profile`](Sh1106::with_profile), or
+    /// otherwise of the default [`InitConfig`], except that the contrast is the
+    /// [`default_contrast`](DisplaySize::default_contrast) of the display size.
+    ///
+    /// The exact sequence sent for two common panels:
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(
+    ///     MockInterface::new(),
+    ///     DisplaySize128x64,
+    ///     DisplayRotation::Rotate0,
+    /// );
+    /// display.init_default().unwrap();
+    ///
+    /// #[rustfmt::skip]
+    /// assert_eq!(
+    ///     display.release().commands(),
+    ///     &[
+    ///         0xAE,       // Display off
+    ///         0xD5, 0x50, // Clock divide ratio and oscillator frequency
+    ///         0xA8, 0x3F, // Multiplex ratio of 64 rows
+    ///         0xD3, 0x00, // Display offset
+    ///         0x40,       // Start line 0
+    ///         0xAD, 0x8B, // Charge pump on
+    ///         0xDA, 0x12, // Alternative COM pin configuration
+    ///         0xA1,       // Segment remap
+    ///         0xC8,       // Reverse COM scan direction
+    ///         0xD9, 0x21, // Pre-charge period
+    ///         0x81, 0x5F, // Contrast
+    ///         0xDB, 0x50, // VCOMH deselect level
+    ///         0xA4,       // Show the display RAM
+    ///         0xA6,       // Not inverted
+    ///         0xAF,       // Display on
+    ///     ]
+    /// );
+    ///
+    /// let mut display = Sh1106::new(
+    ///     MockInterface::new(),
+    ///     DisplaySize72x40,
+    ///     DisplayRotation::Rotate0,
+    /// );
+    /// display.init_default().unwrap();
+    ///
+    /// #[rustfmt::skip]
+    /// assert_eq!(
+    ///     display.release().commands(),
+    ///     &[
+    ///         0xAE,
+    ///         0xD5, 0x50,
+    ///         0xA8, 0x27, // Multiplex ratio of 40 rows
+    ///         0xD3, 0x00,
+    ///         0x40,
+    ///         0xAD, 0x8B,
+    ///         0xDA, 0x12,
+    ///         0xA1,
+    ///         0xC8,
+    ///         0xD9, 0x21,
+    ///         0x81, 0x7F, // Contrast of the 72x40 panel
+    ///         0xDB, 0x50,
+    ///         0xA4,
+    ///         0xA6,
+    ///         0xAF,
+    ///     ]
+    /// );
+    /// ```
+    pub async fn init_default(&mut self) -> Result<(), Error> {
+        let config = self.profile.unwrap_or(InitConfig {
+            brightness: Brightness::custom(Brightness::NORMAL.precharge, SIZE::default_contrast()),
+            ..InitConfig::default()
+        });
 
-        Command::DisplayOn(false).send(&mut self.interface).await?;
-        Command::DisplayClockDiv(0x5, 0x0) //0x5 is default for SH1106
-            .send(&mut self.interface)
-            .await?;
-        Command::Multiplex(SIZE::HEIGHT - 1) //TODO SSD1306 legacy SH1106 behaviour is different
-            .send(&mut self.interface)
-            .await?;
-        Command::DisplayOffset(0).send(&mut self.interface).await?;
-        Command::StartLine(0).send(&mut self.interface).await?;
-        // TODO: Ability to turn charge pump on/off
-        Command::ChargePump(true).send(&mut self.interface).await?;
+        self.init_with_config(config).await
+    }
+
+    /// Run only the command phase of the initialisation: no reset pulse, and no clearing of the
+    /// display RAM or the framebuffer.
+    ///
+    /// This sends the same commands as [`init_default`](Sh1106::init_default), for rigs which
+    /// manage the reset line themselves, for example because it's shared by several displays.
+    /// The driver state afterwards is identical to that after a full initialisation with
+    /// [`reset`](Sh1106::reset) and [`init`](mode::DisplayConfig::init); only the reset pulse and
+    /// the clear are left out. See [`reset`](Sh1106::reset) for the ordering on a shared reset
+    /// line.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::{DelayStub, MockInterface, PinStub};
+    /// # let mut rst = PinStub;
+    /// # let mut delay = DelayStub;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut full =
+    ///     Sh1106::new(MockInterface::new(), DisplaySize128x64, DisplayRotation::Rotate0)
+    ///         .into_buffered_graphics_mode();
+    /// let mut partial =
+    ///     Sh1106::new(MockInterface::new(), DisplaySize128x64, DisplayRotation::Rotate0)
+    ///         .into_buffered_graphics_mode();
+    ///
+    /// full.reset(&mut rst, &mut delay).unwrap();
+    /// full.init().unwrap();
+    ///
+    /// partial.init_commands_only().unwrap();
+    ///
+    /// assert_eq!(full.config(), partial.config());
+    /// assert_eq!(full.release().commands(), partial.release().commands());
+    /// ```
+    pub async fn init_commands_only(&mut self) -> Result<(), Error> {
+        self.init_default().await
+    }
 
-        self.size.configure(&mut self.interface).await?;
-        self.set_rotation(rotation).await?;
+    /// Initialise the display with custom clock, pre-charge, COM pin and charge pump settings.
+    ///
+    /// See [`InitConfig`] for the available settings.
+    ///
+    /// The sequence has no delays. The async driver awaits every command and returns to the
+    /// executor between the phases of the sequence, so other tasks, such as the bring-up of
+    /// sensors on another bus, keep running while a slow bus initialises the display.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display
+    ///     .init_with_config(InitConfig {
+    ///         clock_div: (0x8, 0x0),
+    ///         precharge: Some((0x2, 0x2)),
+    ///         ..InitConfig::default()
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let interface = display.release();
+    /// let commands = interface.commands();
+    ///
+    /// assert_eq!(&commands[1..3], &[0xD5, 0x80]);
+    /// assert!(commands.windows(2).any(|command| command == [0xD9, 0x22]));
+    /// ```
+    pub async fn init_with_config(&mut self, config: InitConfig) -> Result<(), Error> {
+        self.send_init(config)
+            .await
+            .map_err(|error| error.in_stage(Error::Init))
+    }
 
-        self.set_brightness(Brightness::default()).await?;
-        Command::VcomhDeselect(VcomhLevel::default())
-            .send(&mut self.interface)
-            .await?;
-        Command::AllOn(false).send(&mut self.interface).await?;
-        Command::Invert(false).send(&mut self.interface).await?;
-        Command::DisplayOn(true).send(&mut self.interface).await?;
+    /// Get the command bytes [`init_with_config`](Sh1106::init_with_config) would send for the
+    /// size, rotation and mirroring of the display, without sending anything.
+    ///
+    /// The sequence can be sent through a custom transport or logged, and sending it initialises
+    /// the display exactly like `init_with_config`. The driver state isn't changed, so the
+    /// [`config`](Sh1106::config) doesn't reflect the sequence until it is sent with
+    /// `init_with_config`.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate180);
+    ///
+    /// let commands = display.init_commands(InitConfig::default()).unwrap();
+    /// assert_eq!(&commands.as_bytes()[..3], &[0xAE, 0xD5, 0x50]);
+    ///
+    /// display.init_default().unwrap();
+    /// assert_eq!(display.release().commands(), commands.as_bytes());
+    /// ```
+    pub async fn init_commands(&self, config: InitConfig) -> Result<InitCommands, Error> {
+        let mut recorder = InitRecorder::new();
+
+        Self::send_init_sequence(&mut recorder, &self.size, self.remap(), config)
+            .await
+            .map_err(Error::Init)?;
+
+        Ok(recorder.into_commands())
+    }
+
+    async fn send_init(&mut self, config: InitConfig) -> Result<(), Error> {
+        let remap = self.remap();
+
+        Self::send_init_sequence(&mut self.interface, &self.size, remap, config).await?;
+        self.start_line = 0;
+        self.height = SIZE::HEIGHT;
+        self.contrast = config.brightness.contrast;
+        self.invert = false;
+        self.display_on = true;
 
         Ok(())
     }
 
+    /// Send the initialisation sequence for the given segment remap and COM direction.
+    async fn send_init_sequence(
+        iface: &mut impl WriteOnlyDataCommand,
+        size: &SIZE,
+        (segment_remap, reverse_com): (bool, bool),
+        config: InitConfig,
+    ) -> Result<(), DisplayError> {
+        let (fosc, div) = config.clock_div;
+
+        Command::send_batch(
+            iface,
+            &[
+                Command::DisplayOn(false),
+                Command::DisplayClockDiv(fosc, div),
+                Command::Multiplex(SIZE::HEIGHT - 1), //TODO SSD1306 legacy SH1106 behaviour is different
+                Command::DisplayOffset(SIZE::OFFSETY),
+                Command::StartLine(0),
+                Command::ChargePump(config.internal_charge_pump),
+            ],
+        )
+        .await?;
+
+        yield_now().await;
+
+        // The pump voltage is meaningless with an external VPP supply
+        if let Some(voltage) = config.pump_voltage.filter(|_| config.internal_charge_pump) {
+            Command::SetPumpVoltage(voltage).send(iface).await?;
+        }
+
+        match config.com_pin_alt {
+            Some(alt) => Command::ComPinConfig(alt).send(iface).await?,
+            None => size.configure(iface).await?,
+        }
+
+        yield_now().await;
+
+        Command::SegmentRemap(segment_remap).send(iface).await?;
+        Command::ReverseComDir(reverse_com).send(iface).await?;
+
+        let (phase1, phase2) = config.precharge.unwrap_or((1, config.brightness.precharge));
+        Command::PreChargePeriod(phase1, phase2).send(iface).await?;
+        Command::Contrast(config.brightness.contrast)
+            .send(iface)
+            .await?;
+
+        yield_now().await;
+
+        Command::send_batch(
+            iface,
+            &[
+                Command::VcomhDeselect(config.vcomh),
+                Command::AllOn(false),
+                Command::Invert(false),
+                Command::DisplayOn(true),
+            ],
+        )
+        .await
+    }
+
     /// Send the data to the display for drawing at the current position in the framebuffer
     /// and advance the position accordingly. Cf. `set_draw_area` to modify the affected area by
     /// this method.
@@ -274,24 +821,36 @@ where
         disp_width: usize,
         upper_left: (u8, u8),
         lower_right: (u8, u8),
-    ) -> Result<(), DisplayError> {
+    ) -> Result<(), Error> {
+        let column_offset = self.ram_column_offset()?;
+
         Self::flush_buffer_chunks(
             &mut self.interface,
+            self.addressing,
             buffer,
             disp_width,
             upper_left,
             lower_right,
+            column_offset,
+            usize::MAX,
+            FlushOrder::TopDown,
         )
-        .await
+        .await?;
+
+        Ok(())
     }
 
     /// Send a raw buffer to the display.
-    pub async fn draw(&mut self, buffer: &[u8]) -> Result<(), DisplayError> {
-        self.interface.send_data(U8(buffer)).await
+    pub async fn draw(&mut self, buffer: &[u8]) -> Result<(), Error> {
+        self.interface.send_data(U8(buffer)).await?;
+
+        Ok(())
     }
 
     /// Get display dimensions, taking into account the current rotation of the display
     ///
+    /// This is also the size of the embedded-graphics bounding box in the buffered graphics mode.
+    ///
     /// ```rust
     /// # use sh1106::test_helpers::StubInterface;
     /// # let interface = StubInterface;
@@ -313,9 +872,11 @@ where
     /// assert_eq!(rotated_display.dimensions(), (64, 128));
     /// ```
     pub fn dimensions(&self) -> (u8, u8) {
+        let (width, height) = (SIZE::WIDTH, self.height);
+
         match self.rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (SIZE::WIDTH, SIZE::HEIGHT),
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (SIZE::HEIGHT, SIZE::WIDTH),
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (width, height),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (height, width),
         }
     }
 
@@ -324,180 +885,912 @@ where
         self.rotation
     }
 
-    /// Set the display rotation.
-    pub async fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), DisplayError> {
-        self.rotation = rotation;
+    /// Get the display RAM column which corresponds to the first column of the framebuffer.
+    ///
+    /// Returns [`Error::InvalidConfig`] if the column offset puts part of the display past the
+    /// last column of the display RAM.
+    fn ram_column_offset(&self) -> Result<u8, Error> {
+        if usize::from(self.column_offset) + usize::from(SIZE::WIDTH)
+            > usize::from(SIZE::DRIVER_COLS)
+        {
+            return Err(Error::InvalidConfig(
+                "column offset puts the display past the last column of the display RAM",
+            ));
+        }
 
-        match rotation {
-            DisplayRotation::Rotate0 => {
-                Command::SegmentRemap(true)
-                    .send(&mut self.interface)
-                    .await?;
-                Command::ReverseComDir(true)
-                    .send(&mut self.interface)
-                    .await?;
-            }
-            DisplayRotation::Rotate90 => {
-                Command::SegmentRemap(false)
-                    .send(&mut self.interface)
-                    .await?;
-                Command::ReverseComDir(true)
-                    .send(&mut self.interface)
-                    .await?;
-            }
-            DisplayRotation::Rotate180 => {
-                Command::SegmentRemap(false)
-                    .send(&mut self.interface)
-                    .await?;
-                Command::ReverseComDir(false)
-                    .send(&mut self.interface)
-                    .await?;
-            }
-            DisplayRotation::Rotate270 => {
-                Command::SegmentRemap(true)
-                    .send(&mut self.interface)
-                    .await?;
-                Command::ReverseComDir(false)
-                    .send(&mut self.interface)
-                    .await?;
-            }
-        };
+        let (segment_remap, _) = self.remap();
 
-        Ok(())
+        if segment_remap {
+            Ok(self.column_offset)
+        } else {
+            // If segment remapping is flipped, we need to calculate
+            // the offset from the other edge of the display.
+            Ok(SIZE::DRIVER_COLS - SIZE::WIDTH - self.column_offset)
+        }
     }
 
-    /// Set mirror enabled/disabled.
-    pub async fn set_mirror(&mut self, mirror: bool) -> Result<(), DisplayError> {
-        if mirror {
-            match self.rotation {
-                DisplayRotation::Rotate0 => {
-                    Command::SegmentRemap(false)
-                        .send(&mut self.interface)
-                        .await?;
-                    Command::ReverseComDir(true)
-                        .send(&mut self.interface)
-                        .await?;
-                }
-                DisplayRotation::Rotate90 => {
-                    Command::SegmentRemap(false)
-                        .send(&mut self.interface)
-                        .await?;
-                    Command::ReverseComDir(false)
-                        .send(&mut self.interface)
-                        .await?;
-                }
-                DisplayRotation::Rotate180 => {
-                    Command::SegmentRemap(true)
-                        .send(&mut self.interface)
-                        .await?;
-                    Command::ReverseComDir(false)
-                        .send(&mut self.interface)
-                        .await?;
-                }
-                DisplayRotation::Rotate270 => {
-                    Command::SegmentRemap(true)
-                        .send(&mut self.interface)
-                        .await?;
-                    Command::ReverseComDir(true)
-                        .send(&mut self.interface)
-                        .await?;
-                }
-            };
-        } else {
-            self.set_rotation(self.rotation).await?;
+    /// Get the segment remap and reverse COM direction settings for the current rotation and
+    /// mirroring.
+    fn remap(&self) -> (bool, bool) {
+        let (horizontal, vertical) = self.mirror;
+
+        // Rotating by 90 or 270 degrees swaps which of the two flips the logical axes
+        match self.rotation {
+            DisplayRotation::Rotate0 => (!horizontal, !vertical),
+            DisplayRotation::Rotate90 => (vertical, !horizontal),
+            DisplayRotation::Rotate180 => (horizontal, vertical),
+            DisplayRotation::Rotate270 => (!vertical, horizontal),
         }
-        Ok(())
     }
 
-    /// Change the display brightness.
-    pub async fn set_brightness(&mut self, brightness: Brightness) -> Result<(), DisplayError> {
-        Command::PreChargePeriod(1, brightness.precharge)
+    /// Send the segment remap and COM direction for the current rotation and mirroring.
+    async fn send_remap(&mut self) -> Result<(), Error> {
+        let (segment_remap, reverse_com) = self.remap();
+
+        Command::SegmentRemap(segment_remap)
             .send(&mut self.interface)
             .await?;
-        Command::Contrast(brightness.contrast)
+        Command::ReverseComDir(reverse_com)
             .send(&mut self.interface)
-            .await
-    }
-
-    /// Turn the display on or off. The display can be drawn to and retains all
-    /// of its memory even while off.
-    pub async fn set_display_on(&mut self, on: bool) -> Result<(), DisplayError> {
-        Command::DisplayOn(on).send(&mut self.interface).await
-    }
-
-    // /// Set the position in the framebuffer of the display limiting where any sent data should be
-    // /// drawn. This method can be used for changing the affected area on the screen as well
-    // /// as (re-)setting the start point of the next `draw` call.
-    // pub async fn set_draw_area(
-    //     &mut self,
-    //     start: (u8, u8),
-    //     end: (u8, u8),
-    // ) -> Result<(), DisplayError> {
-    //     Command::ColumnAddress(start.0, end.0.saturating_sub(1))
-    //         .send(&mut self.interface)
-    //         .await?;
-    //
-    //     if self.addr_mode != AddrMode::Page {
-    //         Command::PageAddress(start.1.into(), (end.1.saturating_sub(1)).into())
-    //             .send(&mut self.interface)
-    //             .await?;
-    //     }
-    //
-    //     Ok(())
-    // }
+            .await?;
 
-    /// Set the column address in the framebuffer of the display where any sent data should be
-    /// drawn.
-    pub async fn set_column(&mut self, column: u8) -> Result<(), DisplayError> {
-        Command::ColStart(column).send(&mut self.interface).await
+        Ok(())
     }
 
-    /// Set the page address (row 8px high) in the framebuffer of the display where any sent data
-    /// should be drawn.
+    /// Set the display rotation.
     ///
-    /// Note that the parameter is in pixels, but the page will be set to the start of the 8px
-    /// row which contains the passed-in row.
-    pub async fn set_row(&mut self, row: u8) -> Result<(), DisplayError> {
-        Command::PageStart(row.into())
-            .send(&mut self.interface)
-            .await
-    }
+    /// This can be called at any time after [`init_default`](Sh1106::init_default). The
+    /// [`dimensions`](Sh1106::dimensions) are updated immediately, so the width and height swap
+    /// when changing between `Rotate0`/`Rotate180` and `Rotate90`/`Rotate270`.
+    ///
+    /// In [`BufferedGraphicsMode`], the next [`flush`](Sh1106::flush) sends the whole framebuffer
+    /// again using the new rotation.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use embedded_graphics::prelude::*;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.flush().unwrap();
+    ///
+    /// display.set_rotation(DisplayRotation::Rotate90).unwrap();
+    /// assert_eq!(display.bounding_box().size, Size::new(64, 128));
+    ///
+    /// display.flush().unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // One page for the first flush, then the remap commands and all 8 pages
+    /// assert_eq!(interface.commands().len(), 3 + 2 + 8 * 3);
+    /// assert_eq!(interface.data().len(), 1 + 128 * 64 / 8);
+    /// ```
+    pub async fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), Error> {
+        self.rotation = rotation;
 
-    /// Set the screen pixel on/off inversion
-    pub async fn set_invert(&mut self, invert: bool) -> Result<(), DisplayError> {
-        Command::Invert(invert).send(&mut self.interface).await
+        self.send_remap().await
     }
 
+    /// Mirror the display horizontally and/or vertically, independently of the rotation.
+    ///
+    /// Mirroring is applied on top of the rotation in the rotated coordinate system, so a
+    /// horizontal mirror always swaps the left and right of [`dimensions`](Sh1106::dimensions).
+    /// Mirroring both axes has the same effect as rotating by a further 180 degrees. The setting
+    /// is kept when the rotation is changed.
+    ///
+    /// Only the way the display RAM is scanned changes, so the image is mirrored without
+    /// redrawing it. For panels which don't show a centred window of the display RAM, the visible
+    /// window moves as well; call [`mark_all_dirty`](Sh1106::mark_all_dirty) and
+    /// [`flush`](Sh1106::flush) to redraw the buffer in that case.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// for (horizontal, vertical, segment_remap, com_dir) in [
+    ///     (false, false, 0xA1, 0xC8),
+    ///     (true, false, 0xA0, 0xC8),
+    ///     (false, true, 0xA1, 0xC0),
+    ///     (true, true, 0xA0, 0xC0),
+    /// ] {
+    ///     let mut display =
+    ///         Sh1106::new(MockInterface::new(), DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    ///     display.set_mirror(horizontal, vertical).unwrap();
+    ///
+    ///     assert_eq!(display.release().commands(), &[segment_remap, com_dir]);
+    /// }
+    /// ```
+    pub async fn set_mirror(&mut self, horizontal: bool, vertical: bool) -> Result<(), Error> {
+        self.mirror = (horizontal, vertical);
+
+        self.send_remap().await
+    }
+
+    /// Change the display brightness.
+    pub async fn set_brightness(&mut self, brightness: Brightness) -> Result<(), Error> {
+        Command::PreChargePeriod(1, brightness.precharge)
+            .send(&mut self.interface)
+            .await?;
+        self.set_contrast(brightness.contrast).await
+    }
+
+    /// Set the display contrast. Higher values are brighter. The default after
+    /// [`init_default`](Sh1106::init_default) is the
+    /// [`default_contrast`](DisplaySize::default_contrast) of the display size.
+    ///
+    /// Unlike [`set_brightness`](Sh1106::set_brightness), this only changes the contrast and
+    /// leaves the pre-charge period untouched, which makes it suitable for fine-grained
+    /// brightness control.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    /// display.set_contrast(0x40).unwrap();
+    ///
+    /// assert_eq!(display.release().commands(), &[0x81, 0x40]);
+    /// ```
+    pub async fn set_contrast(&mut self, contrast: u8) -> Result<(), Error> {
+        Command::Contrast(contrast)
+            .send(&mut self.interface)
+            .await?;
+        self.contrast = contrast;
+
+        Ok(())
+    }
+
+    /// Set the display brightness as a percentage from 0 to 100. Larger values are clamped to
+    /// 100.
+    ///
+    /// The perceived brightness of the panel is not linear in the contrast value, so the
+    /// percentage is mapped onto the contrast register with a gamma 2.2 curve, `contrast =
+    /// round(255 * (percent / 100) ^ 2.2)`. For example 25% is a contrast of 12, 50% is 55 and
+    /// 75% is 135. Like [`set_contrast`](Sh1106::set_contrast), the pre-charge period is left
+    /// untouched.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display.set_brightness_percent(50).unwrap();
+    /// assert_eq!(display.contrast(), 55);
+    ///
+    /// display.set_brightness_percent(150).unwrap();
+    /// assert_eq!(display.contrast(), 255);
+    ///
+    /// assert_eq!(display.release().commands(), &[0x81, 55, 0x81, 255]);
+    /// ```
+    pub async fn set_brightness_percent(&mut self, percent: u8) -> Result<(), Error> {
+        self.set_contrast(brightness::percent_to_contrast(percent))
+            .await
+    }
+
+    /// Get the last contrast set by [`set_contrast`](Sh1106::set_contrast),
+    /// [`set_brightness`](Sh1106::set_brightness) or [`fade_to`](Sh1106::fade_to).
+    ///
+    /// Before any of them is called, this is the
+    /// [`default_contrast`](DisplaySize::default_contrast) of the display size used by
+    /// [`init_default`](Sh1106::init_default).
+    pub fn contrast(&self) -> u8 {
+        self.contrast
+    }
+
+    /// Gradually change the contrast from its current value to `target`, in increments of
+    /// `step`.
+    ///
+    /// `delay` is used to wait 4ms after each step. A `step` of 0 sets the target contrast
+    /// immediately.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::{DelayStub, MockInterface};
+    /// # let interface = MockInterface::new();
+    /// # let mut delay = DelayStub;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    /// display.set_contrast(0x10).unwrap();
+    ///
+    /// display.fade_to(0x30, 0x0C, &mut delay).unwrap();
+    /// assert_eq!(display.contrast(), 0x30);
+    ///
+    /// // The last step is shortened to stop at the target
+    /// assert_eq!(
+    ///     display.release().commands(),
+    ///     &[0x81, 0x10, 0x81, 0x1C, 0x81, 0x28, 0x81, 0x30]
+    /// );
+    /// ```
+    pub async fn fade_to<DELAY>(
+        &mut self,
+        target: u8,
+        step: u8,
+        delay: &mut DELAY,
+    ) -> Result<(), Error>
+    where
+        DELAY: DelayNs,
+    {
+        if step == 0 {
+            return self.set_contrast(target).await;
+        }
+
+        while self.contrast != target {
+            let contrast = if self.contrast < target {
+                self.contrast.saturating_add(step).min(target)
+            } else {
+                self.contrast.saturating_sub(step).max(target)
+            };
+
+            self.set_contrast(contrast).await?;
+            delay.delay_ms(FADE_STEP_DELAY_MS).await;
+        }
+
+        Ok(())
+    }
+
+    /// Turn the display on or off. The display can be drawn to and retains all
+    /// of its memory even while off.
+    pub async fn set_display_on(&mut self, on: bool) -> Result<(), Error> {
+        Command::DisplayOn(on).send(&mut self.interface).await?;
+        self.display_on = on;
+
+        Ok(())
+    }
+
+    /// Put the display to sleep by turning it off and disabling the internal DC-DC converter
+    /// (charge pump).
+    ///
+    /// According to the SH1106 datasheet, the controller draws only a few microamps in sleep
+    /// mode, compared to several milliamps when displaying an image, depending on how many pixels
+    /// are lit. The display RAM and the framebuffer of the buffered graphics mode are retained,
+    /// so [`wake`](Sh1106::wake) shows the last flushed image again without re-running
+    /// [`init_default`](Sh1106::init_default).
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display.sleep().unwrap();
+    /// display.wake().unwrap();
+    ///
+    /// assert_eq!(
+    ///     display.release().commands(),
+    ///     &[0xAE, 0xAD, 0x8A, 0xAD, 0x8B, 0xAF]
+    /// );
+    /// ```
+    pub async fn sleep(&mut self) -> Result<(), Error> {
+        Command::DisplayOn(false).send(&mut self.interface).await?;
+        self.display_on = false;
+        Command::ChargePump(false).send(&mut self.interface).await?;
+
+        Ok(())
+    }
+
+    /// Wake the display from [`sleep`](Sh1106::sleep) by enabling the internal DC-DC converter
+    /// (charge pump) and turning the display on.
+    ///
+    /// The datasheet recommends waiting 100ms between enabling the DC-DC converter and turning the
+    /// display on. This method doesn't wait, which works fine with most panels.
+    pub async fn wake(&mut self) -> Result<(), Error> {
+        Command::ChargePump(true).send(&mut self.interface).await?;
+        Command::DisplayOn(true).send(&mut self.interface).await?;
+        self.display_on = true;
+
+        Ok(())
+    }
+
+    /// Run through a sequence of test patterns to check every pixel of the panel.
+    ///
+    /// Each step is shown for 500ms, waiting with `delay`:
+    ///
+    /// 1. All pixels on, using [`set_all_on`](Sh1106::set_all_on)
+    /// 2. All pixels off, by turning the display off
+    /// 3. All pixels on again, by inverting the cleared display RAM with
+    ///    [`set_invert`](Sh1106::set_invert)
+    /// 4. A checkerboard of single pixels
+    ///
+    /// Dead rows or columns show up in the first and third steps, and stuck or shorted pixels in
+    /// the others.
+    ///
+    /// Afterwards the inversion and on/off state the display had before the test are sent again,
+    /// even if one of the steps fails. This works in every mode, but the display RAM can't be read
+    /// back, so it's left cleared rather than restored. In [`BufferedGraphicsMode`], call
+    /// [`mark_all_dirty`](Sh1106::mark_all_dirty) and [`flush`](Sh1106::flush) afterwards to show
+    /// the framebuffer again.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::{DelayStub, MockInterface};
+    /// # let interface = MockInterface::new();
+    /// # let mut delay = DelayStub;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display.set_invert(true).unwrap();
+    /// display.self_test(&mut delay).unwrap();
+    ///
+    /// // The display is still inverted, and still off as it was never initialised
+    /// assert!(display.config().invert);
+    /// assert!(!display.config().display_on);
+    ///
+    /// let interface = display.release();
+    /// let commands = interface.commands();
+    ///
+    /// // Display on and all pixels on, then display off
+    /// assert_eq!(&commands[1..4], &[0xAF, 0xA5, 0xAE]);
+    /// // Normal mode, inverted and off again
+    /// assert_eq!(&commands[commands.len() - 3..], &[0xA4, 0xA7, 0xAE]);
+    /// // Every column of every page is cleared, filled with the checkerboard and cleared again
+    /// assert_eq!(interface.data().len(), 3 * 8 * 132);
+    /// assert_eq!(&interface.data()[8 * 132..][..4], &[0x55, 0xAA, 0x55, 0xAA]);
+    /// ```
+    ///
+    /// When the display RAM can't be written, the state is still restored:
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::{DelayStub, MockInterface};
+    /// # let mut delay = DelayStub;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut interface = MockInterface::new();
+    /// // Fail the first write of the test patterns, and the clear afterwards
+    /// interface.fail_data(2);
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display.set_invert(true).unwrap();
+    /// assert!(display.self_test(&mut delay).is_err());
+    ///
+    /// let interface = display.release();
+    /// let commands = interface.commands();
+    ///
+    /// assert!(interface.data().is_empty());
+    /// // Normal mode, inverted and off again after the failed clear
+    /// assert_eq!(&commands[commands.len() - 3..], &[0xA4, 0xA7, 0xAE]);
+    /// ```
+    pub async fn self_test<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), Error>
+    where
+        DELAY: DelayNs,
+    {
+        let invert = self.invert;
+        let display_on = self.display_on;
+
+        let result = self.run_self_test(delay).await;
+
+        // Restore the previous state even if a step failed, reporting the first error
+        let restored = self.restore_after_self_test(invert, display_on).await;
+
+        self.invert = invert;
+        self.display_on = display_on;
+
+        result.and(restored)
+    }
+
+    /// The test patterns of [`self_test`](Sh1106::self_test), leaving the display in any state.
+    async fn run_self_test<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), Error>
+    where
+        DELAY: DelayNs,
+    {
+        const STEP_MS: u32 = 500;
+
+        Command::DisplayOn(true).send(&mut self.interface).await?;
+        Command::AllOn(true).send(&mut self.interface).await?;
+        delay.delay_ms(STEP_MS).await;
+
+        // Clear the display RAM while the display is off, so the inverted step is all on
+        Command::DisplayOn(false).send(&mut self.interface).await?;
+        self.fill_ram([0x00, 0x00]).await?;
+        delay.delay_ms(STEP_MS).await;
+
+        Command::AllOn(false).send(&mut self.interface).await?;
+        Command::Invert(true).send(&mut self.interface).await?;
+        Command::DisplayOn(true).send(&mut self.interface).await?;
+        delay.delay_ms(STEP_MS).await;
+
+        Command::Invert(false).send(&mut self.interface).await?;
+        self.fill_ram([0x55, 0xAA]).await?;
+        delay.delay_ms(STEP_MS).await;
+
+        Ok(())
+    }
+
+    /// Clear the display RAM left by [`self_test`](Sh1106::self_test) and resend the given
+    /// inversion and on/off state.
+    ///
+    /// Every step is tried even if an earlier one fails, and the first error is returned.
+    async fn restore_after_self_test(
+        &mut self,
+        invert: bool,
+        display_on: bool,
+    ) -> Result<(), Error> {
+        let cleared = self.fill_ram([0x00, 0x00]).await;
+        let all_on = Command::AllOn(false).send(&mut self.interface).await;
+        let inverted = Command::Invert(invert).send(&mut self.interface).await;
+        let on = Command::DisplayOn(display_on)
+            .send(&mut self.interface)
+            .await;
+
+        cleared.and(all_on.and(inverted).and(on).map_err(Error::from))
+    }
+
+    /// Fill every column of the display RAM, including those outside the panel, with a repeating
+    /// pair of bytes.
+    async fn fill_ram(&mut self, pattern: [u8; 2]) -> Result<(), Error> {
+        // 6 repeats of the pattern, the last chunk of a page is cut short if the columns don't
+        // divide evenly
+        let mut chunk = [0; 12];
+        for pair in chunk.chunks_exact_mut(2) {
+            pair.copy_from_slice(&pattern);
+        }
+
+        for page in 0..SIZE::DRIVER_ROWS / 8 {
+            let mut commands = [0; 4];
+            let commands_len = self.addressing.address_page(page, 0, &mut commands).min(4);
+            self.interface
+                .send_commands(U8(&commands[..commands_len]))
+                .await?;
+
+            let mut remaining = SIZE::DRIVER_COLS as usize;
+            while remaining > 0 {
+                let len = remaining.min(chunk.len());
+                self.interface.send_data(U8(&chunk[..len])).await?;
+                remaining -= len;
+            }
+        }
+
+        Ok(())
+    }
+
+    // /// Set the position in the framebuffer of the display limiting where any sent data should be
+    // /// drawn. This method can be used for changing the affected area on the screen as well
+    // /// as (re-)setting the start point of the next `draw` call.
+    // pub async fn set_draw_area(
+    //     &mut self,
+    //     start: (u8, u8),
+    //     end: (u8, u8),
+    // ) -> Result<(), Error> {
+    //     Command::ColumnAddress(start.0, end.0.saturating_sub(1))
+    //         .send(&mut self.interface)
+    //         .await?;
+    //
+    //     if self.addr_mode != AddrMode::Page {
+    //         Command::PageAddress(start.1.into(), (end.1.saturating_sub(1)).into())
+    //             .send(&mut self.interface)
+    //             .await?;
+    //     }
+    //
+    //     Ok(())
+    // }
+
+    /// Set the column address in the framebuffer of the display where any sent data should be
+    /// drawn.
+    ///
+    /// The column is relative to the visible area of the display, so the display RAM column
+    /// offset is added before it is sent. Columns which end up past the last column of the
+    /// display RAM return [`Error::InvalidConfig`] instead of wrapping around.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Error, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// // The panel starts at display RAM column 2, so this is the last column, 131
+    /// display.set_column(129).unwrap();
+    /// assert!(matches!(display.set_column(130), Err(Error::InvalidConfig(_))));
+    ///
+    /// assert_eq!(display.release().commands(), &[0x03, 0x18]);
+    /// ```
+    pub async fn set_column(&mut self, column: u8) -> Result<(), Error> {
+        let column = self
+            .ram_column_offset()?
+            .checked_add(column)
+            .filter(|column| *column < SIZE::DRIVER_COLS)
+            .ok_or(Error::InvalidConfig(
+                "column is past the last column of the display RAM",
+            ))?;
+
+        Command::ColStart(column).send(&mut self.interface).await?;
+
+        Ok(())
+    }
+
+    /// Set the page address (row 8px high) in the framebuffer of the display where any sent data
+    /// should be drawn.
+    ///
+    /// Note that the parameter is in pixels, but the page will be set to the start of the 8px
+    /// row which contains the passed-in row. Rows past the last page of the display RAM return
+    /// [`Error::InvalidConfig`]. Displays with more than 64 rows of display RAM, such as
+    /// `DisplaySize128x128`, get page addresses past `0xB7`.
+    pub async fn set_row(&mut self, row: u8) -> Result<(), Error> {
+        if row >= SIZE::DRIVER_ROWS {
+            return Err(command::OutOfRange.into());
+        }
+
+        match Page::from_row(row) {
+            Ok(page) => Command::PageStart(page).send(&mut self.interface).await?,
+            // Past the 8 pages of the SH1106, which the page command can't express
+            Err(_) => {
+                self.interface
+                    .send_commands(U8(&[0xB0 | (row / 8)]))
+                    .await?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the display RAM row shown at the top of the display, from 0 to 63. Larger values are
+    /// clamped to 63.
+    ///
+    /// On the SH1107 of `DisplaySize128x128` the row goes up to 127, and is sent with the
+    /// SH1107's `0xDC` command instead, as the SH1106 command only has 6 bits for it.
+    ///
+    /// This pans the display vertically without changing the display RAM, and rows scrolled off
+    /// the top wrap around to the bottom. It is cheap enough to run every frame for smooth
+    /// scrolling. The framebuffer and [`flush`](Sh1106::flush) are unaffected and still work in
+    /// display RAM coordinates.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display.set_start_line(12).unwrap();
+    /// display.set_start_line(100).unwrap();
+    /// assert_eq!(display.start_line(), 63);
+    ///
+    /// assert_eq!(display.release().commands(), &[0x4C, 0x7F]);
+    /// ```
+    pub async fn set_start_line(&mut self, line: u8) -> Result<(), Error> {
+        let line = line.min(SIZE::DRIVER_ROWS - 1);
+
+        if SIZE::DRIVER_ROWS > 64 {
+            self.interface.send_commands(U8(&[0xDC, line])).await?;
+        } else {
+            Command::StartLine(line).send(&mut self.interface).await?;
+        }
+        self.start_line = line;
+
+        Ok(())
+    }
+
+    /// Set the number of display rows in use and the display offset together.
+    ///
+    /// This is for panels with an unusual height, which only show some of the rows of their
+    /// [`DisplaySize`]. `rows` is sent as the multiplex ratio and must be from 1 to the
+    /// [`DRIVER_ROWS`](DisplaySize::DRIVER_ROWS) of the display size, 64 on the SH1106, and
+    /// `offset` is the COM line the first row is shown on and must be below `DRIVER_ROWS`.
+    /// Values out of range return [`Error::InvalidConfig`] without sending anything. Setting both
+    /// at once keeps the image from being partially lit or shifted in between.
+    ///
+    /// The height returned by [`dimensions`](Sh1106::dimensions), and used for drawing and
+    /// flushing, becomes `rows`, up to the height of the display size. Initialising the display
+    /// resets the rows and the offset to those of the display size.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Error, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display.set_active_area(48, 8).unwrap();
+    /// assert_eq!(display.dimensions(), (128, 48));
+    ///
+    /// assert!(matches!(
+    ///     display.set_active_area(0, 0),
+    ///     Err(Error::InvalidConfig(_))
+    /// ));
+    /// assert!(matches!(
+    ///     display.set_active_area(64, 64),
+    ///     Err(Error::InvalidConfig(_))
+    /// ));
+    ///
+    /// assert_eq!(display.release().commands(), &[0xA8, 47, 0xD3, 8]);
+    /// ```
+    pub async fn set_active_area(&mut self, rows: u8, offset: u8) -> Result<(), Error> {
+        if !(1..=SIZE::DRIVER_ROWS).contains(&rows) {
+            return Err(Error::InvalidConfig(
+                "active area must have at least 1 row and fit in the display RAM",
+            ));
+        }
+
+        if offset >= SIZE::DRIVER_ROWS {
+            return Err(Error::InvalidConfig(
+                "display offset is past the last row of the display RAM",
+            ));
+        }
+
+        Command::send_batch(
+            &mut self.interface,
+            &[Command::Multiplex(rows - 1), Command::DisplayOffset(offset)],
+        )
+        .await?;
+        self.height = rows.min(SIZE::HEIGHT);
+
+        Ok(())
+    }
+
+    /// Get the display RAM row shown at the top of the display, as last set by
+    /// [`set_start_line`](Sh1106::set_start_line) or initialisation.
+    pub fn start_line(&self) -> u8 {
+        self.start_line
+    }
+
+    /// Get a snapshot of the display settings last sent by the driver, for example to log them
+    /// while debugging the setup code.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display.init_default().unwrap();
+    /// display.set_invert(true).unwrap();
+    /// display.set_mirror(true, false).unwrap();
+    ///
+    /// assert_eq!(
+    ///     display.config(),
+    ///     DisplayConfigState {
+    ///         contrast: 0x5F,
+    ///         rotation: DisplayRotation::Rotate0,
+    ///         invert: true,
+    ///         display_on: true,
+    ///         start_line: 0,
+    ///         mirror_horizontal: true,
+    ///         mirror_vertical: false,
+    ///     }
+    /// );
+    /// ```
+    pub fn config(&self) -> DisplayConfigState {
+        DisplayConfigState {
+            contrast: self.contrast,
+            rotation: self.rotation,
+            invert: self.invert,
+            display_on: self.display_on,
+            start_line: self.start_line,
+            mirror_horizontal: self.mirror.0,
+            mirror_vertical: self.mirror.1,
+        }
+    }
+
+    /// Set the screen pixel on/off inversion
+    ///
+    /// Only the way the display RAM is shown changes, so turning inversion off again restores
+    /// the original image.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display.set_invert(true).unwrap();
+    /// display.set_all_on(true).unwrap();
+    /// display.set_all_on(false).unwrap();
+    /// display.set_invert(false).unwrap();
+    ///
+    /// assert_eq!(display.release().commands(), &[0xA7, 0xA5, 0xA4, 0xA6]);
+    /// ```
+    pub async fn set_invert(&mut self, invert: bool) -> Result<(), Error> {
+        Command::Invert(invert).send(&mut self.interface).await?;
+        self.invert = invert;
+
+        Ok(())
+    }
+
+    /// Turn all pixels on, regardless of the contents of the display RAM. This is useful for
+    /// testing a panel for dead pixels or burn-in.
+    ///
+    /// The display RAM is not modified, so turning this off again restores the original image.
+    pub async fn set_all_on(&mut self, on: bool) -> Result<(), Error> {
+        Command::AllOn(on).send(&mut self.interface).await?;
+
+        Ok(())
+    }
+
+    /// Check that the display is still connected by sending a single
+    /// [`Noop`](Command::Noop) command.
+    ///
+    /// The SH1106 buses can't read anything back, so this only reports what the interface
+    /// notices about the write. Over I2C, a display which doesn't acknowledge its address, for
+    /// example because the cable was unplugged, makes the write fail with an error. Over SPI,
+    /// writes succeed whether or not a display is connected, so this can't detect a missing
+    /// display.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// if display.ping().is_err() {
+    ///     // Show "display disconnected" elsewhere
+    /// }
+    ///
+    /// assert_eq!(display.release().commands(), &[0xE3]);
+    /// ```
+    pub async fn ping(&mut self) -> Result<(), Error> {
+        Command::Noop.send(&mut self.interface).await?;
+
+        Ok(())
+    }
+
+    /// Send raw command bytes to the display, bypassing the typed [`Command`] API.
+    ///
+    /// This is an escape hatch for replaying captured init sequences or for vendor-specific
+    /// commands of compatible controllers, which [`Command`] doesn't cover. The bytes are sent in
+    /// transfers of at most 7 bytes, as that is all the I2C interface can send at once.
+    ///
+    /// The driver doesn't know what the bytes do, so they can leave the display in a state which
+    /// doesn't match what the driver believes, for example the rotation, contrast or display RAM
+    /// address. Prefer [`Command::send`] and the typed methods wherever possible.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    ///
+    /// display.send_raw_commands(&[0xAD, 0x8B, 0x32]).unwrap();
+    /// display.send_raw_data(&[0xFF, 0x00]).unwrap();
+    ///
+    /// let interface = display.release();
+    /// assert_eq!(interface.commands(), &[0xAD, 0x8B, 0x32]);
+    /// assert_eq!(interface.data(), &[0xFF, 0x00]);
+    /// ```
+    pub async fn send_raw_commands(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        for chunk in bytes.chunks(7) {
+            self.interface.send_commands(U8(chunk)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Send raw bytes to the display RAM at the current address, bypassing the framebuffer.
+    ///
+    /// Like [`send_raw_commands`](Sh1106::send_raw_commands), this can leave the display RAM out
+    /// of sync with the framebuffer of the buffered graphics mode.
+    pub async fn send_raw_data(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.interface.send_data(U8(bytes)).await?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn flush_buffer_chunks(
         interface: &mut DI,
+        addressing: &dyn AddressingStrategy,
         buffer: &[u8],
         disp_width: usize,
         upper_left: (u8, u8),
         lower_right: (u8, u8),
-    ) -> Result<(), DisplayError> {
-        // Divide by 8 since each row is actually 8 pixels tall
-        let num_pages = ((lower_right.1 - upper_left.1) / 8) as usize + 1;
-
-        // Each page is 8 bits tall, so calculate which page number to start at (rounded down) from
-        // the top of the display
-        let starting_page = (upper_left.1 / 8) as usize;
+        column_offset: u8,
+        chunk_size: usize,
+        order: FlushOrder,
+    ) -> Result<FlushStats, Error> {
+        let mut stats = FlushStats::default();
 
         // Calculate start and end X coordinates for each page
         let page_lower = upper_left.0 as usize;
         let page_upper = lower_right.0 as usize;
 
-        for c in buffer
-            .chunks(disp_width)
-            .skip(starting_page)
-            .take(num_pages)
-            .map(|s| &s[page_lower..page_upper])
-        {
-            interface.send_data(U8(c)).await?
+        // Each page is 8 bits tall, so divide by 8 to get the first and last page
+        for page in order.pages(upper_left.1 / 8, lower_right.1 / 8) {
+            let start = page as usize * disp_width;
+
+            let c = match buffer.get(start + page_lower..start + page_upper) {
+                Some(c) => c,
+                // The buffer doesn't reach this page
+                None => continue,
+            };
+
+            // The SH1106 only supports page addressing, so the position has to be set for every
+            // page
+            Self::send_run(
+                interface,
+                addressing,
+                page,
+                usize::from(column_offset) + usize::from(upper_left.0),
+                c,
+                chunk_size,
+            )
+            .await?;
+
+            if !c.is_empty() {
+                stats.bytes_sent += c.len();
+                stats.pages_sent += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Write `data` to `page` of the display RAM starting at RAM `column`, in transfers of at
+    /// most `chunk_size` bytes. The address is set as often as `addressing` requires.
+    ///
+    /// Returns [`Error::InvalidConfig`] without sending anything if the run doesn't fit in the
+    /// display RAM, for example because the column offset is too large for the display width.
+    async fn send_run(
+        interface: &mut DI,
+        addressing: &dyn AddressingStrategy,
+        page: u8,
+        column: usize,
+        mut data: &[u8],
+        chunk_size: usize,
+    ) -> Result<(), Error> {
+        if column + data.len() > usize::from(SIZE::DRIVER_COLS) {
+            return Err(Error::InvalidConfig(
+                "column offset puts the display past the last column of the display RAM",
+            ));
         }
+
+        // The check above keeps every column of the run below `DRIVER_COLS`
+        let mut column = column as u8;
+
+        while !data.is_empty() {
+            let len = addressing
+                .run_length(column, data.len())
+                .clamp(1, data.len());
+            let (run, rest) = data.split_at(len);
+
+            let mut commands = [0; 4];
+            let commands_len = addressing.address_page(page, column, &mut commands).min(4);
+            interface
+                .send_commands(U8(&commands[..commands_len]))
+                .await?;
+
+            for chunk in run.chunks(chunk_size.max(1)) {
+                interface.send_data(U8(chunk)).await?
+            }
+
+            column += len as u8;
+            data = rest;
+        }
+
         Ok(())
     }
 
     /// Release the contained interface.
+    ///
+    /// This is available in every mode and consumes the driver without sending anything, so the
+    /// display keeps showing its last image and the interface can be handed to another driver.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    ///
+    /// // Unflushed changes are discarded
+    /// let interface = display.release();
+    /// assert!(interface.commands().is_empty());
+    /// assert!(interface.data().is_empty());
+    /// ```
     pub fn release(self) -> DI {
         self.interface
     }
@@ -517,12 +1810,62 @@ where
     )
 )]
 impl<DI, SIZE, MODE> Sh1106<DI, SIZE, MODE> {
-    /// Reset the display.
-    pub async fn reset<RST, DELAY>(
-        &mut self,
-        rst: &mut RST,
-        delay: &mut DELAY,
-    ) -> Result<(), Error<Infallible, RST::Error>>
+    /// Reset the display using its RES pin.
+    ///
+    /// This pulses the reset pin low for 10ms, then waits another 10ms for the controller to
+    /// come out of reset. Call this before [`init`](crate::mode::DisplayConfig::init). Errors
+    /// setting the pin are returned as [`Error::Init`] with [`DisplayError::RSError`].
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::{DelayStub, PinStub, StubInterface};
+    /// # let interface = StubInterface;
+    /// # let mut rst = PinStub;
+    /// # let mut delay = DelayStub;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.reset(&mut rst, &mut delay).unwrap();
+    /// display.init().unwrap();
+    /// ```
+    ///
+    /// [`init_commands_only`](Sh1106::init_commands_only), like
+    /// [`init`](crate::mode::DisplayConfig::init) and [`init_default`](Sh1106::init_default),
+    /// never touches the reset pin, so displays sharing a reset line are reset once, then each
+    /// initialised in turn. Finish the reset before initialising any of them, as a reset pulse
+    /// in between would undo the initialisation of the displays before it. Every display ends up
+    /// in the same state as when it has a reset line of its own:
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::{DelayStub, MockInterface, PinStub};
+    /// # let mut rst = PinStub;
+    /// # let mut delay = DelayStub;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut left = Sh1106::new(
+    ///     MockInterface::new(),
+    ///     DisplaySize128x64,
+    ///     DisplayRotation::Rotate0,
+    /// )
+    /// .into_buffered_graphics_mode();
+    /// let mut right = Sh1106::new(
+    ///     MockInterface::new(),
+    ///     DisplaySize128x64,
+    ///     DisplayRotation::Rotate0,
+    /// )
+    /// .into_buffered_graphics_mode();
+    ///
+    /// // Pulse the shared reset line once, through either driver
+    /// left.reset(&mut rst, &mut delay).unwrap();
+    ///
+    /// left.init_commands_only().unwrap();
+    /// right.init_commands_only().unwrap();
+    ///
+    /// assert_eq!(left.config(), right.config());
+    /// assert_eq!(left.release().commands(), right.release().commands());
+    /// ```
+    pub async fn reset<RST, DELAY>(&mut self, rst: &mut RST, delay: &mut DELAY) -> Result<(), Error>
     where
         RST: OutputPin,
         DELAY: DelayNs,
@@ -536,9 +1879,14 @@ impl<DI, SIZE, MODE> Sh1106<DI, SIZE, MODE> {
             delay.delay_ms(1).await;
             rst.set_low()?;
             delay.delay_ms(10).await;
-            rst.set_high()
+            rst.set_high()?;
+            delay.delay_ms(10).await;
+
+            Ok(())
         }
 
-        inner_reset(rst, delay).await.map_err(Error::Pin)
+        inner_reset(rst, delay)
+            .await
+            .map_err(|_| Error::Init(DisplayError::RSError))
     }
 }
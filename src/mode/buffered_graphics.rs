@@ -1,15 +1,126 @@
 //! Buffered graphics mode.
 
+use crate::Error;
+#[cfg(feature = "graphics")]
+use crate::RmwInterface;
+#[cfg(all(feature = "graphics", feature = "async"))]
+use crate::RmwInterfaceAsync;
 use crate::{
+    addressing::AddressingStrategy,
+    command::Command,
     rotation::DisplayRotation,
-    size::{DisplaySize, NewZeroed},
+    size::{
+        DisplaySize, DisplaySize128x32, DisplaySize128x64, DisplaySize64x32, DisplaySize64x48,
+        DisplaySize72x40, DisplaySize96x16, NewZeroed,
+    },
     Sh1106,
 };
 #[cfg(feature = "async")]
-use crate::{size::DisplaySizeAsync, Sh1106Async};
+use crate::{command::CommandAsync, size::DisplaySizeAsync, Sh1106Async};
+use core::{marker::PhantomData, ops::Range};
 #[cfg(feature = "async")]
 use display_interface::AsyncWriteOnlyDataCommand;
-use display_interface::{DisplayError, WriteOnlyDataCommand};
+use display_interface::WriteOnlyDataCommand;
+
+/// Storage for the copy of the display RAM used by [`Sh1106::into_diff_mode`].
+///
+/// This is implemented by [`NoShadow`], which disables diffing, and [`DiffShadow`].
+pub trait Shadow {
+    /// Create the storage.
+    fn new_shadow() -> Self;
+
+    /// Get the copy of the display RAM, or `None` if flushes are not diffed.
+    fn as_shadow_mut(&mut self) -> Option<&mut [u8]>;
+}
+
+/// No copy of the display RAM. This is the default for [`BufferedGraphicsMode`].
+#[derive(Debug, Copy, Clone)]
+pub struct NoShadow;
+
+impl Shadow for NoShadow {
+    fn new_shadow() -> Self {
+        NoShadow
+    }
+
+    fn as_shadow_mut(&mut self) -> Option<&mut [u8]> {
+        None
+    }
+}
+
+/// A copy of the display RAM the size of the framebuffer, used to only send changed bytes.
+#[derive(Debug, Clone)]
+pub struct DiffShadow<B>(B);
+
+impl<B> Shadow for DiffShadow<B>
+where
+    B: AsMut<[u8]> + NewZeroed,
+{
+    fn new_shadow() -> Self {
+        DiffShadow(B::new_zeroed())
+    }
+
+    fn as_shadow_mut(&mut self) -> Option<&mut [u8]> {
+        Some(self.0.as_mut())
+    }
+}
+
+/// Order in which [`flush`](Sh1106::flush) sends the pages of the changed area, see
+/// [`set_flush_order`](Sh1106::set_flush_order).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum FlushOrder {
+    /// From the top page to the bottom page of the display RAM, the default
+    #[default]
+    TopDown,
+    /// From the bottom page to the top page of the display RAM
+    BottomUp,
+    /// The even pages from the top down, then the odd pages from the top down
+    Interlaced,
+}
+
+impl FlushOrder {
+    /// Get the pages from `first` to `last`, inclusive, in this order.
+    pub(crate) fn pages(self, first: u8, last: u8) -> impl Iterator<Item = u8> {
+        // The first even and odd page in the range
+        let even = first + first % 2;
+        let odd = first + 1 - first % 2;
+        let evens = if even <= last {
+            (last - even) / 2 + 1
+        } else {
+            0
+        };
+
+        (0..=last - first).map(move |index| match self {
+            FlushOrder::TopDown => first + index,
+            FlushOrder::BottomUp => last - index,
+            FlushOrder::Interlaced if index < evens => even + index * 2,
+            FlushOrder::Interlaced => odd + (index - evens) * 2,
+        })
+    }
+}
+
+/// Amount of display data sent by a flush, returned by
+/// [`flush_with_stats`](Sh1106::flush_with_stats).
+///
+/// Only the framebuffer bytes are counted, not the commands setting the display RAM address.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct FlushStats {
+    /// Number of framebuffer bytes sent
+    pub bytes_sent: usize,
+    /// Number of pages at least one byte was sent to
+    pub pages_sent: u8,
+}
+
+/// How drawing maps colors to pixels of the framebuffer, see
+/// [`set_color_mode`](Sh1106::set_color_mode).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// `BinaryColor::On` and `true` turn pixels on, the default
+    #[default]
+    Normal,
+    /// `BinaryColor::On` and `true` turn pixels off, and `BinaryColor::Off` and `false` turn
+    /// them on
+    Inverted,
+}
 
 /// Buffered graphics mode.
 ///
@@ -17,40 +128,191 @@ use display_interface::{DisplayError, WriteOnlyDataCommand};
 /// buffer is drawn to by [`set_pixel`](Sh1106::set_pixel) commands or
 /// [`embedded-graphics`](https://docs.rs/embedded-graphics) commands. The display can then be
 /// updated using the [`flush`](Sh1106::flush) method.
+///
+/// The area of the buffer changed since the last flush is tracked, so `flush` only sends the
+/// pages and columns which have actually changed, and nothing at all if the buffer is unchanged.
+/// [`into_diff_mode`](Sh1106::into_diff_mode) additionally keeps a copy of the display RAM in
+/// `SHADOW`, so only the bytes which differ from the last flush are sent.
+///
+/// The framebuffer `BUF` is an array stored in the driver by default. Use
+/// [`into_buffered_graphics_mode_with_buffer`](Sh1106::into_buffered_graphics_mode_with_buffer)
+/// to provide the memory yourself instead.
 #[maybe_async_cfg::maybe(
     sync(keep_self),
     async(feature = "async", idents(DisplaySize(async = "DisplaySizeAsync")))
 )]
 #[derive(Clone, Debug)]
-pub struct BufferedGraphicsMode<SIZE>
+pub struct BufferedGraphicsMode<SIZE, SHADOW = NoShadow, BUF = <SIZE as DisplaySize>::Buffer>
 where
     SIZE: DisplaySize,
 {
-    buffer: SIZE::Buffer,
+    buffer: BUF,
+    size: PhantomData<SIZE>,
+    // Copy of the display RAM for diffing flushes, see `Sh1106::into_diff_mode`
+    shadow: SHADOW,
+    // Whether the shadow matches the display RAM outside of the changed area
+    shadow_valid: bool,
     min_x: u8,
     max_x: u8,
     min_y: u8,
     max_y: u8,
+    // Rotation used by the last flush. The whole buffer is sent again when this changes.
+    flushed_rotation: DisplayRotation,
+    // Only read by the blocking driver, as `DrawTarget` cannot await a flush
+    #[allow(dead_code)]
+    auto_flush: bool,
+    flush_order: FlushOrder,
+    color_mode: ColorMode,
+    // Number of unchanged flushes before dimming and the contrast to dim to, see
+    // `Sh1106::set_idle_dim`
+    idle_dim: Option<(u32, u8)>,
+    // Number of flushes since the framebuffer last changed
+    idle_flushes: u32,
+    // Whether the contrast has been lowered by idle dimming
+    dimmed: bool,
+}
+
+/// Buffered graphics mode drawing into a caller-provided buffer, see
+/// [`into_buffered_graphics_mode_with_buffer`](Sh1106::into_buffered_graphics_mode_with_buffer).
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(BufferedGraphicsMode(async = "BufferedGraphicsModeAsync"))
+    )
+)]
+pub type BufferedGraphicsModeRef<'a, SIZE> = BufferedGraphicsMode<SIZE, NoShadow, &'a mut [u8]>;
+
+/// Driver for a display of the given size in buffered graphics mode.
+///
+/// The framebuffer is an array of exactly `WIDTH * HEIGHT / 8` bytes for the size, given by
+/// [`DisplaySize::Buffer`], so smaller panels use less RAM. The aliases below spell out the
+/// driver type for each size.
+///
+/// ```rust
+/// # use sh1106::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use sh1106::{prelude::*, Sh1106};
+///
+/// let display: Sh1106_72x40<_> = Sh1106::new(interface, DisplaySize72x40, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// assert_eq!(display.buffer().len(), 360);
+/// assert_eq!(
+///     core::mem::size_of::<<DisplaySize72x40 as DisplaySize>::Buffer>(),
+///     360
+/// );
+/// ```
+pub type Sh1106Buffered<DI, SIZE> = Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE>>;
+
+/// Driver for a 128x64 display in buffered graphics mode, with a 1024 byte framebuffer.
+#[allow(non_camel_case_types)]
+pub type Sh1106_128x64<DI> = Sh1106Buffered<DI, DisplaySize128x64>;
+
+/// Driver for a 128x32 display in buffered graphics mode, with a 512 byte framebuffer.
+#[allow(non_camel_case_types)]
+pub type Sh1106_128x32<DI> = Sh1106Buffered<DI, DisplaySize128x32>;
+
+/// Driver for a 96x16 display in buffered graphics mode, with a 192 byte framebuffer.
+#[allow(non_camel_case_types)]
+pub type Sh1106_96x16<DI> = Sh1106Buffered<DI, DisplaySize96x16>;
+
+/// Driver for a 72x40 display in buffered graphics mode, with a 360 byte framebuffer.
+#[allow(non_camel_case_types)]
+pub type Sh1106_72x40<DI> = Sh1106Buffered<DI, DisplaySize72x40>;
+
+/// Driver for a 64x48 display in buffered graphics mode, with a 384 byte framebuffer.
+#[allow(non_camel_case_types)]
+pub type Sh1106_64x48<DI> = Sh1106Buffered<DI, DisplaySize64x48>;
+
+/// Driver for a 64x32 display in buffered graphics mode, with a 256 byte framebuffer.
+#[allow(non_camel_case_types)]
+pub type Sh1106_64x32<DI> = Sh1106Buffered<DI, DisplaySize64x32>;
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(feature = "async", idents(DisplaySize(async = "DisplaySizeAsync")))
+)]
+impl<SIZE, SHADOW> BufferedGraphicsMode<SIZE, SHADOW>
+where
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+{
+    /// Create a new buffered graphics mode instance for a display with the given rotation.
+    pub(crate) fn new(rotation: DisplayRotation) -> Self {
+        Self::with_buffer(rotation, NewZeroed::new_zeroed())
+    }
 }
 
 #[maybe_async_cfg::maybe(
     sync(keep_self),
     async(feature = "async", idents(DisplaySize(async = "DisplaySizeAsync")))
 )]
-impl<SIZE> BufferedGraphicsMode<SIZE>
+impl<SIZE, SHADOW, BUF> BufferedGraphicsMode<SIZE, SHADOW, BUF>
 where
     SIZE: DisplaySize,
+    SHADOW: Shadow,
 {
-    /// Create a new buffered graphics mode instance.
-    pub(crate) fn new() -> Self {
+    /// Create a new buffered graphics mode instance for a display with the given rotation,
+    /// drawing into `buffer`.
+    pub(crate) fn with_buffer(rotation: DisplayRotation, buffer: BUF) -> Self {
         Self {
-            buffer: NewZeroed::new_zeroed(),
+            buffer,
+            size: PhantomData,
+            shadow: SHADOW::new_shadow(),
+            shadow_valid: false,
             min_x: 255,
             max_x: 0,
             min_y: 255,
             max_y: 0,
+            flushed_rotation: rotation,
+            auto_flush: false,
+            flush_order: FlushOrder::TopDown,
+            color_mode: ColorMode::Normal,
+            idle_dim: None,
+            idle_flushes: 0,
+            dimmed: false,
         }
     }
+
+    /// Extend the changed area to include the given logical coordinate.
+    fn mark_dirty(&mut self, x: u8, y: u8) {
+        self.min_x = self.min_x.min(x);
+        self.max_x = self.max_x.max(x);
+        self.min_y = self.min_y.min(y);
+        self.max_y = self.max_y.max(y);
+    }
+
+    /// Mark the whole buffer as changed, for a logical display size of `width` by `height`.
+    fn mark_all_dirty(&mut self, width: u8, height: u8) {
+        self.min_x = 0;
+        self.max_x = width - 1;
+        self.min_y = 0;
+        self.max_y = height - 1;
+    }
+
+    /// Whether any area has been marked as changed.
+    fn is_dirty(&self) -> bool {
+        self.min_x <= self.max_x && self.min_y <= self.max_y
+    }
+
+    /// Get the upper left and lower right corners of the changed area, inclusive, and reset it.
+    ///
+    /// Returns `None` if nothing has changed since the last call.
+    fn take_dirty(&mut self) -> Option<((u8, u8), (u8, u8))> {
+        let dirty = if self.is_dirty() {
+            Some(((self.min_x, self.min_y), (self.max_x, self.max_y)))
+        } else {
+            None
+        };
+
+        self.min_x = 255;
+        self.max_x = 0;
+        self.min_y = 255;
+        self.max_y = 0;
+
+        dirty
+    }
 }
 
 #[maybe_async_cfg::maybe(
@@ -65,22 +327,29 @@ where
         )
     )
 )]
-impl<DI, SIZE> DisplayConfig for Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE>>
+impl<DI, SIZE, SHADOW, BUF> DisplayConfig
+    for Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
 where
     DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
 {
-    type Error = DisplayError;
+    type Error = Error;
 
     /// Set the display rotation
     ///
     /// This method resets the cursor but does not clear the screen.
-    async fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DisplayError> {
+    async fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), Error> {
         self.set_rotation(rot).await
     }
 
     /// Initialise and clear the display in graphics mode.
-    async fn init(&mut self) -> Result<(), DisplayError> {
+    ///
+    /// Only the framebuffer is cleared, so the display RAM is cleared by the next
+    /// [`flush`](Sh1106::flush), and the display RAM columns outside the visible area of the
+    /// panel are never cleared. Use [`reinit`](Sh1106::reinit) to clear all of the display RAM.
+    async fn init(&mut self) -> Result<(), Error> {
         self.clear_impl(false);
         self.init_default().await
     }
@@ -91,203 +360,2043 @@ where
     async(
         feature = "async",
         idents(
+            Command(async = "CommandAsync"),
             DisplaySize(async = "DisplaySizeAsync"),
             WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
             BufferedGraphicsMode(async = "BufferedGraphicsModeAsync")
         )
     )
 )]
-impl<DI, SIZE> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE>>
+impl<DI, SIZE, SHADOW, BUF> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
 where
     DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
 {
     fn clear_impl(&mut self, value: bool) {
-        self.mode.buffer.as_mut().fill(if value { 0xff } else { 0 });
-
-        let (width, height) = self.dimensions();
-        self.mode.min_x = 0;
-        self.mode.max_x = width - 1;
-        self.mode.min_y = 0;
-        self.mode.max_y = height - 1;
+        self.clear_pattern(if value { 0xff } else { 0 });
     }
 
     /// Clear the underlying framebuffer. You need to call `disp.flush()` for any effect on the screen.
+    ///
+    /// The whole display is sent on the next flush.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.clear_buffer();
+    /// display.flush().unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // Page and column address for each of the 8 pages, followed by the page data
+    /// assert_eq!(interface.commands().len(), 8 * 3);
+    /// assert_eq!(interface.data().len(), 128 * 64 / 8);
+    /// ```
     pub fn clear_buffer(&mut self) {
         self.clear_impl(false);
     }
 
-    /// Write out data to a display.
+    /// Fill every byte of the framebuffer with `pattern`, a vertical strip of 8 pixels with the
+    /// least significant bit at the top. You need to call `disp.flush()` for any effect on the
+    /// screen.
     ///
-    /// This only updates the parts of the display that have changed since the last flush.
-    pub async fn flush(&mut self) -> Result<(), DisplayError> {
-        // Nothing to do if no pixels have changed since the last update
-        if self.mode.max_x < self.mode.min_x || self.mode.max_y < self.mode.min_y {
-            return Ok(());
-        }
+    /// This is a cheap way to draw stripes or dotted backgrounds without embedded-graphics. For
+    /// `Rotate90` and `Rotate270` the strip runs horizontally instead.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // Every other row on
+    /// display.clear_pattern(0xAA);
+    ///
+    /// assert!(display.buffer().iter().all(|byte| *byte == 0xAA));
+    /// ```
+    pub fn clear_pattern(&mut self, pattern: u8) {
+        self.mode.buffer.as_mut().fill(pattern);
+        self.mark_buffer_dirty();
+    }
 
-        let (width, height) = self.dimensions();
+    /// Get the framebuffer.
+    ///
+    /// The buffer uses the page layout of the SH1106 display RAM: each byte is a vertical strip
+    /// of 8 pixels, with the least significant bit at the top. The bytes of a page (8 pixel rows)
+    /// are stored left to right, followed by the next page. The pixel at `(x, y)` is therefore bit
+    /// `y % 8` of byte `(y / 8) * width + x`. For `Rotate90` and `Rotate270` the axes are swapped,
+    /// see [`set_pixel`](Sh1106::set_pixel).
+    pub fn buffer(&self) -> &[u8] {
+        self.mode.buffer.as_ref()
+    }
 
-        // Determine which bytes need to be sent
-        let disp_min_x = self.mode.min_x;
-        let disp_min_y = self.mode.min_y;
+    /// Get the framebuffer for modification. See [`buffer`](Sh1106::buffer) for the layout.
+    ///
+    /// Changes made through this slice can't be tracked, so the whole framebuffer is marked as
+    /// changed and sent on the next [`flush`](Sh1106::flush).
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// // A pre-rendered image in page layout
+    /// let image = [0b1010_1010; 128 * 64 / 8];
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.buffer_mut().copy_from_slice(&image);
+    /// display.flush().unwrap();
+    ///
+    /// assert_eq!(display.release().data(), &image);
+    /// ```
+    pub fn buffer_mut(&mut self) -> &mut [u8] {
+        self.mark_buffer_dirty();
 
-        let (disp_max_x, disp_max_y) = match self.rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (
-                (self.mode.max_x + 1).min(width),
-                (self.mode.max_y | 7).min(height),
-            ),
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (
-                (self.mode.max_x | 7).min(width),
-                (self.mode.max_y + 1).min(height),
-            ),
-        };
+        self.mode.buffer.as_mut()
+    }
+
+    /// Move the contents of the framebuffer up by `pixels` rows, filling the rows at the bottom
+    /// with off pixels. You need to call `disp.flush()` for any effect on the screen.
+    ///
+    /// The direction is relative to the current rotation. The whole display is sent on the next
+    /// flush.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 20, true);
+    ///
+    /// // Page aligned, from bit 4 of page 2 to bit 4 of page 1
+    /// display.scroll_up(8);
+    /// assert_eq!(display.buffer()[128], 0b0001_0000);
+    ///
+    /// // Within a page, to row 9
+    /// display.scroll_up(3);
+    /// assert_eq!(display.buffer()[128], 0b0000_0010);
+    ///
+    /// // Across a page boundary, to row 6
+    /// display.scroll_up(3);
+    /// assert_eq!(display.buffer()[128], 0);
+    /// assert_eq!(display.buffer()[0], 0b0100_0000);
+    ///
+    /// // Rows scrolled off the top are lost
+    /// display.scroll_up(7);
+    /// assert!(display.buffer().iter().all(|byte| *byte == 0));
+    /// ```
+    ///
+    /// With [`set_active_area`](Sh1106::set_active_area), only the rows in use are scrolled, and
+    /// the rows below them are left alone:
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // Rows 43 and 44 are lit, and there are 44 rows in use
+    /// display.buffer_mut()[5 * 128] = 0b0001_1000;
+    /// display.set_active_area(44, 0).unwrap();
+    ///
+    /// // Row 43 moves up to row 41, and row 44 stays where it is instead of moving into view
+    /// display.scroll_up(2);
+    /// assert_eq!(display.buffer()[5 * 128], 0b0001_0010);
+    /// ```
+    pub fn scroll_up(&mut self, pixels: u32) {
+        self.scroll(pixels, true);
+    }
+
+    /// Move the contents of the framebuffer down by `pixels` rows, filling the rows at the top
+    /// with off pixels. You need to call `disp.flush()` for any effect on the screen.
+    ///
+    /// The direction is relative to the current rotation. The whole display is sent on the next
+    /// flush.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 6, true);
+    ///
+    /// // Across a page boundary, to row 9
+    /// display.scroll_down(3);
+    /// assert_eq!(display.buffer()[0], 0);
+    /// assert_eq!(display.buffer()[128], 0b0000_0010);
+    ///
+    /// // Page aligned, to row 17
+    /// display.scroll_down(8);
+    /// assert_eq!(display.buffer()[128], 0);
+    /// assert_eq!(display.buffer()[256], 0b0000_0010);
+    /// ```
+    pub fn scroll_down(&mut self, pixels: u32) {
+        self.scroll(pixels, false);
+    }
 
-        self.mode.min_x = 255;
-        self.mode.max_x = 0;
-        self.mode.min_y = 255;
-        self.mode.max_y = 0;
-
-        // // Tell the display to update only the part that has changed
-        // let offset_x = match self.rotation {
-        //     DisplayRotation::Rotate0 | DisplayRotation::Rotate270 => SIZE::OFFSETX,
-        //     DisplayRotation::Rotate180 | DisplayRotation::Rotate90 => {
-        //         // If segment remapping is flipped, we need to calculate
-        //         // the offset from the other edge of the display.
-        //         SIZE::DRIVER_COLS - SIZE::WIDTH - SIZE::OFFSETX
-        //     }
-        // };
+    fn scroll(&mut self, pixels: u32, up: bool) {
+        let (_, height) = self.dimensions();
+
+        // Scrolling by the whole height or more moves every row out
+        let pixels = pixels.min(u32::from(height)) as usize;
+        let width = SIZE::WIDTH as usize;
+        let buffer = self.mode.buffer.as_mut();
 
         match self.rotation {
+            // Rows are bits in a column of bytes, one byte per page. Each byte takes the bits
+            // shifted in from its neighbour on the side the rows move away from. Only the pages
+            // of the active area are shifted, and rows below it are read as off and kept as they
+            // are, so they aren't scrolled into view.
             DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                // self.set_draw_area(
-                //     (disp_min_x + offset_x, disp_min_y + SIZE::OFFSETY),
-                //     (disp_max_x + offset_x, disp_max_y + SIZE::OFFSETY),
-                // )
-                // .await?;
+                let pages = usize::from(height.div_ceil(8));
+                let (page_shift, bit_shift) = (pixels / 8, pixels % 8);
+                // Bits of the last page inside the active area
+                let last_mask = match height % 8 {
+                    0 => 0xFF,
+                    rows => (1u8 << rows) - 1,
+                };
+                let mask = |page: usize| if page + 1 == pages { last_mask } else { 0xFF };
 
-                Self::flush_buffer_chunks(
-                    &mut self.interface,
-                    self.mode.buffer.as_mut(),
-                    width as usize,
-                    (disp_min_x, disp_min_y),
-                    (disp_max_x, disp_max_y),
-                )
-                .await
+                for column in 0..width {
+                    let byte = |buffer: &[u8], page: usize| {
+                        if page < pages {
+                            buffer[page * width + column] & mask(page)
+                        } else {
+                            0
+                        }
+                    };
+                    let store = |buffer: &mut [u8], page: usize, value: u8| {
+                        let old = &mut buffer[page * width + column];
+                        *old = value & mask(page) | *old & !mask(page);
+                    };
+
+                    if up {
+                        for page in 0..pages {
+                            let low = byte(buffer, page + page_shift);
+                            let high = byte(buffer, page + page_shift + 1);
+
+                            let value = match bit_shift {
+                                0 => low,
+                                _ => low >> bit_shift | high << (8 - bit_shift),
+                            };
+                            store(buffer, page, value);
+                        }
+                    } else {
+                        for page in (0..pages).rev() {
+                            let (high, low) = match page.checked_sub(page_shift) {
+                                Some(source) => (
+                                    byte(buffer, source),
+                                    source.checked_sub(1).map_or(0, |page| byte(buffer, page)),
+                                ),
+                                None => (0, 0),
+                            };
+
+                            let value = match bit_shift {
+                                0 => high,
+                                _ => high << bit_shift | low >> (8 - bit_shift),
+                            };
+                            store(buffer, page, value);
+                        }
+                    }
+                }
             }
+            // Rows are the bytes of each page
             DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                // self.set_draw_area(
-                //     (disp_min_y + offset_x, disp_min_x + SIZE::OFFSETY),
-                //     (disp_max_y + offset_x, disp_max_x + SIZE::OFFSETY),
-                // )
-                // .await?;
-
-                Self::flush_buffer_chunks(
-                    &mut self.interface,
-                    self.mode.buffer.as_mut(),
-                    height as usize,
-                    (disp_min_y, disp_min_x),
-                    (disp_max_y, disp_max_x),
-                )
-                .await
+                for page in buffer.chunks_exact_mut(width) {
+                    if up {
+                        page.copy_within(pixels.., 0);
+                        page[width - pixels..].fill(0);
+                    } else {
+                        page.copy_within(..width - pixels, pixels);
+                        page[..pixels].fill(0);
+                    }
+                }
             }
         }
-    }
 
-    /// Turn a pixel on or off. A non-zero `value` is treated as on, `0` as off. If the X and Y
-    /// coordinates are out of the bounds of the display, this method call is a noop.
-    pub fn set_pixel(&mut self, x: u32, y: u32, value: bool) {
-        let value = value as u8;
-        let rotation = self.rotation;
+        self.mark_buffer_dirty();
+    }
 
-        let (idx, bit) = match rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                let idx = ((y as usize) / 8 * SIZE::WIDTH as usize) + (x as usize);
-                let bit = y % 8;
+    /// Mark the whole framebuffer as changed, so the next [`flush`](Sh1106::flush) sends all of
+    /// it to the display.
+    ///
+    /// Use this when the display RAM no longer matches the framebuffer, for example after
+    /// writing to it directly. In [diff mode](Sh1106::into_diff_mode) the next flush then sends
+    /// every byte, not just those which differ from the last flush.
+    pub fn mark_all_dirty(&mut self) {
+        self.mark_buffer_dirty();
+        self.mode.shadow_valid = false;
+    }
 
-                (idx, bit)
-            }
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                let idx = ((x as usize) / 8 * SIZE::WIDTH as usize) + (y as usize);
-                let bit = x % 8;
+    /// Mark the whole framebuffer as changed, without assuming that the display RAM has changed.
+    fn mark_buffer_dirty(&mut self) {
+        let (width, height) = self.dimensions();
+        self.mode.mark_all_dirty(width, height);
+    }
 
-                (idx, bit)
-            }
-        };
+    /// Initialise the display again, clear all of its RAM and then flush the whole framebuffer,
+    /// for example when recovering from a brownout.
+    ///
+    /// Unlike [`init`](DisplayConfig::init), the framebuffer is kept, and zeros are written to
+    /// every column of every page of the display RAM, all 132 columns of the 8 pages of the
+    /// SH1106, including those outside the visible area of the panel. This keeps stale data left
+    /// in the display RAM from showing at the edges of the panel, for example after the column
+    /// offset or rotation has changed.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.reinit().unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // The whole display RAM is cleared, then the whole framebuffer is sent
+    /// assert_eq!(interface.data().len(), 8 * 132 + 128 * 64 / 8);
+    /// assert_eq!(interface.data()[8 * 132], 0b0000_0001);
+    /// ```
+    pub async fn reinit(&mut self) -> Result<(), Error> {
+        self.init_default().await?;
+        self.fill_ram([0x00, 0x00]).await?;
 
-        if let Some(byte) = self.mode.buffer.as_mut().get_mut(idx) {
-            // Keep track of max and min values
-            self.mode.min_x = self.mode.min_x.min(x as u8);
-            self.mode.max_x = self.mode.max_x.max(x as u8);
+        self.mark_all_dirty();
+        self.flush().await
+    }
 
-            self.mode.min_y = self.mode.min_y.min(y as u8);
-            self.mode.max_y = self.mode.max_y.max(y as u8);
+    /// Write out data to a display.
+    ///
+    /// This only updates the parts of the display that have changed since the last flush.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(10, 9, true);
+    /// display.flush().unwrap();
+    ///
+    /// // Nothing has changed since the last flush, so nothing is sent
+    /// display.flush().unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // Page 1, column 12, as the 128x64 panel starts at display RAM column 2
+    /// assert_eq!(interface.commands(), &[0xB1, 0x0C, 0x10]);
+    /// assert_eq!(interface.data(), &[0b0000_0010]);
+    /// ```
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        self.flush_chunked(usize::MAX).await
+    }
 
-            // Set pixel value in byte
-            // Ref this comment https://stackoverflow.com/questions/47981/how-do-you-set-clear-and-toggle-a-single-bit#comment46654671_47990
-            *byte = *byte & !(1 << bit) | (value << bit);
-        }
+    /// Change the order in which [`flush`](Sh1106::flush) sends the pages of the changed area.
+    ///
+    /// The final image is the same for every order; only the sequence of page writes changes.
+    /// Fast animations can show a visible tear where the panel scans out a page which hasn't been
+    /// written yet, and flushing in a different order can move or hide the tear for full-frame
+    /// updates. Whether this helps depends on the panel and the speed of the bus.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{mode::FlushOrder, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_flush_order(FlushOrder::Interlaced);
+    ///
+    /// // One pixel in each of the pages 0 to 3
+    /// for page in 0..4 {
+    ///     display.set_pixel(0, page * 8, true);
+    /// }
+    /// display.flush().unwrap();
+    ///
+    /// let pages: Vec<u8> = display
+    ///     .release()
+    ///     .commands()
+    ///     .chunks(3)
+    ///     .map(|command| command[0])
+    ///     .collect();
+    ///
+    /// assert_eq!(pages, [0xB0, 0xB2, 0xB1, 0xB3]);
+    /// ```
+    pub fn set_flush_order(&mut self, order: FlushOrder) {
+        self.mode.flush_order = order;
     }
-}
 
-#[cfg(feature = "graphics")]
-use embedded_graphics_core::{
-    draw_target::DrawTarget,
-    geometry::Size,
-    geometry::{Dimensions, OriginDimensions},
-    pixelcolor::BinaryColor,
-    Pixel,
-};
+    /// Get the order in which [`flush`](Sh1106::flush) sends the pages of the changed area.
+    pub fn flush_order(&self) -> FlushOrder {
+        self.mode.flush_order
+    }
 
-use super::DisplayConfig;
-#[cfg(feature = "async")]
-use super::DisplayConfigAsync;
+    /// Set how drawing maps colors to pixels of the framebuffer.
+    ///
+    /// With [`ColorMode::Inverted`], [`set_pixel`](Sh1106::set_pixel) and the embedded-graphics
+    /// draw calls turn pixels off for `true` and `BinaryColor::On`, and on for `false` and
+    /// `BinaryColor::Off`. This makes it possible to draw light-on-dark and dark-on-light content
+    /// on the same screen, such as a highlighted menu item next to normal ones, with the same
+    /// drawing code. Only pixels drawn after the change are affected, and the raw framebuffer
+    /// access of [`buffer_mut`](Sh1106::buffer_mut), [`clear_buffer`](Sh1106::clear_buffer) and
+    /// [`clear_pattern`](Sh1106::clear_pattern) is unchanged.
+    ///
+    /// This is different from [`set_invert`](Sh1106::set_invert), which makes the display
+    /// controller invert the whole panel in hardware without changing the display RAM and costs
+    /// nothing to switch, but can't be limited to part of the screen.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+    /// use sh1106::{mode::ColorMode, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(StubInterface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_color_mode(ColorMode::Inverted);
+    ///
+    /// // A lit box with a dark pixel in it
+    /// display
+    ///     .fill_solid(&Rectangle::new(Point::zero(), Size::new(4, 8)), BinaryColor::Off)
+    ///     .unwrap();
+    /// display.set_pixel(1, 0, true);
+    ///
+    /// assert_eq!(&display.buffer()[0..5], &[0xFF, 0xFE, 0xFF, 0xFF, 0x00]);
+    ///
+    /// // Drawing the same pixel in normal mode turns it back on
+    /// display.set_color_mode(ColorMode::Normal);
+    /// display.set_pixel(1, 0, true);
+    ///
+    /// assert_eq!(display.buffer()[1], 0xFF);
+    /// ```
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.mode.color_mode = mode;
+    }
 
-#[cfg(feature = "graphics")]
-#[maybe_async_cfg::maybe(
-    sync(keep_self),
-    async(
-        feature = "async",
-        idents(
-            DisplaySize(async = "DisplaySizeAsync"),
-            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync"),
-            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand")
-        )
-    )
-)]
-impl<DI, SIZE> DrawTarget for Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE>>
-where
-    DI: WriteOnlyDataCommand,
-    SIZE: DisplaySize,
-{
-    type Color = BinaryColor;
-    type Error = DisplayError;
+    /// Get how drawing maps colors to pixels of the framebuffer.
+    pub fn color_mode(&self) -> ColorMode {
+        self.mode.color_mode
+    }
 
-    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
-    where
-        I: IntoIterator<Item = Pixel<Self::Color>>,
-    {
-        let bb = self.bounding_box();
+    /// Get whether a pixel drawn as `value` is turned on in the framebuffer.
+    pub(crate) fn pixel_value(&self, value: bool) -> bool {
+        value != (self.mode.color_mode == ColorMode::Inverted)
+    }
 
-        pixels
-            .into_iter()
-            .filter(|Pixel(pos, _color)| bb.contains(*pos))
-            .for_each(|Pixel(pos, color)| {
-                self.set_pixel(pos.x as u32, pos.y as u32, color.is_on());
-            });
+    /// Write out data to a display, sending at most `chunk` bytes of each page per transfer.
+    ///
+    /// This sends the same bytes as [`flush`](Sh1106::flush), split into smaller transfers. With
+    /// the async driver, other tasks can run between each transfer instead of waiting for a whole
+    /// page to be sent, and some DMA-backed interfaces have a limit on the transfer size. A
+    /// `chunk` of 0 is treated as 1.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.clear_pattern(0x0F);
+    /// display.flush_chunked(32).unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // The address is only set once per page
+    /// assert_eq!(interface.commands().len(), 8 * 3);
+    /// assert_eq!(interface.data(), &[0x0F; 1024]);
+    /// ```
+    pub async fn flush_chunked(&mut self, chunk: usize) -> Result<(), Error> {
+        self.flush_chunked_with_stats(chunk).await?;
 
         Ok(())
     }
 
-    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
-        self.clear_impl(color.is_on());
-        Ok(())
+    /// Write out data to a display like [`flush`](Sh1106::flush), and return how much data was
+    /// sent.
+    ///
+    /// This shows how well the changed area tracking and
+    /// [`into_diff_mode`](Sh1106::into_diff_mode) work for a given workload. The counts are kept
+    /// by every flush anyway, so this costs nothing extra. Measuring how long the flush takes is
+    /// left to the clock of the caller.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{mode::FlushStats, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // Columns 10 to 20 in pages 0 and 1
+    /// display.set_pixel(10, 0, true);
+    /// display.set_pixel(20, 8, true);
+    ///
+    /// let stats = display.flush_with_stats().unwrap();
+    /// assert_eq!(
+    ///     stats,
+    ///     FlushStats {
+    ///         bytes_sent: 22,
+    ///         pages_sent: 2
+    ///     }
+    /// );
+    ///
+    /// // Nothing has changed
+    /// assert_eq!(display.flush_with_stats().unwrap(), FlushStats::default());
+    /// ```
+    pub async fn flush_with_stats(&mut self) -> Result<FlushStats, Error> {
+        self.flush_chunked_with_stats(usize::MAX).await
     }
-}
 
-#[cfg(feature = "graphics")]
-#[maybe_async_cfg::maybe(
-    sync(keep_self,),
+    async fn flush_chunked_with_stats(&mut self, chunk: usize) -> Result<FlushStats, Error> {
+        // The rotation has changed since the last flush, so the whole buffer has to be sent using
+        // the new mapping
+        if self.mode.flushed_rotation != self.rotation {
+            self.mark_all_dirty();
+            self.mode.flushed_rotation = self.rotation;
+        }
+
+        match self.mode.take_dirty() {
+            Some((upper_left, lower_right)) => {
+                self.mode.idle_flushes = 0;
+
+                if self.mode.dimmed {
+                    // Restore the contrast last set explicitly, which dimming doesn't change
+                    if let Err(error) = Command::Contrast(self.contrast)
+                        .send(&mut self.interface)
+                        .await
+                    {
+                        self.mode.mark_dirty(upper_left.0, upper_left.1);
+                        self.mode.mark_dirty(lower_right.0, lower_right.1);
+
+                        return Err(error.into());
+                    }
+
+                    self.mode.dimmed = false;
+                }
+
+                let result = self.flush_area(upper_left, lower_right, chunk).await;
+
+                if result.is_ok() {
+                    // Everything outside of the changed area was already up to date
+                    self.mode.shadow_valid = true;
+                } else {
+                    // Keep the area marked as changed so the next flush tries again. The display
+                    // may not have received bytes already copied to the shadow, so every byte of
+                    // the area is sent next time.
+                    self.mode.mark_dirty(upper_left.0, upper_left.1);
+                    self.mode.mark_dirty(lower_right.0, lower_right.1);
+                    self.mode.shadow_valid = false;
+                }
+
+                result
+            }
+            // Nothing to send if no pixels have changed since the last update, but the display
+            // may have been idle for long enough to dim it
+            None => {
+                self.mode.idle_flushes = self.mode.idle_flushes.saturating_add(1);
+
+                match self.mode.idle_dim {
+                    Some((after, contrast))
+                        if !self.mode.dimmed && self.mode.idle_flushes >= after =>
+                    {
+                        Command::Contrast(contrast)
+                            .send(&mut self.interface)
+                            .await?;
+                        self.mode.dimmed = true;
+
+                        Ok(FlushStats::default())
+                    }
+                    _ => Ok(FlushStats::default()),
+                }
+            }
+        }
+    }
+
+    /// Lower the contrast to `dim_contrast` once [`flush`](Sh1106::flush) has been called
+    /// `after_flushes` times in a row without the framebuffer having changed, for example to save
+    /// power on a kiosk display nobody is interacting with.
+    ///
+    /// The next flush with changes restores the contrast last set with
+    /// [`set_contrast`](Sh1106::set_contrast) or the initialisation before sending the new
+    /// content, as dimming doesn't change it. Idle dimming is off by default, and an
+    /// `after_flushes` of 0 turns it off again. A display which is already dimmed stays dimmed
+    /// until the next flush with changes.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_idle_dim(2, 0x10);
+    /// display.set_contrast(0xC0).unwrap();
+    ///
+    /// display.flush().unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// // Drawing restores the contrast before flushing the new pixel
+    /// display.set_pixel(0, 0, true);
+    /// display.flush().unwrap();
+    ///
+    /// assert_eq!(
+    ///     display.release().commands(),
+    ///     &[0x81, 0xC0, 0x81, 0x10, 0x81, 0xC0, 0xB0, 0x02, 0x10]
+    /// );
+    /// ```
+    pub fn set_idle_dim(&mut self, after_flushes: u32, dim_contrast: u8) {
+        self.mode.idle_dim = if after_flushes == 0 {
+            None
+        } else {
+            Some((after_flushes, dim_contrast))
+        };
+        self.mode.idle_flushes = 0;
+    }
+
+    /// Write out data to a display like [`flush`](Sh1106::flush), trying again if sending fails.
+    ///
+    /// `attempts` is the total number of tries, and 0 is treated as 1. Each attempt sends the
+    /// whole changed area again, starting with the page and column address of every page, so a
+    /// transfer that failed halfway through doesn't shift the image. The error from the last
+    /// attempt is returned if they all fail.
+    ///
+    /// This helps with occasional NACKs on long or marginal I2C buses, but is only a mitigation.
+    /// Frequent errors usually mean the wiring or pull-up resistors need fixing.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let mut interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// // The first data transfer is lost
+    /// interface.fail_data(1);
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.flush_retry(3).unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // The address is sent again before the second attempt
+    /// assert_eq!(interface.commands(), &[0xB0, 0x02, 0x10, 0xB0, 0x02, 0x10]);
+    /// assert_eq!(interface.data(), &[0x01]);
+    /// ```
+    pub async fn flush_retry(&mut self, attempts: u8) -> Result<(), Error> {
+        let mut attempt = 1;
+
+        loop {
+            match self.flush().await {
+                Err(_) if attempt < attempts => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+
+    /// Flush the framebuffer, then put the display to [`sleep`](Sh1106::sleep), for static
+    /// screens which are only updated occasionally.
+    ///
+    /// Sleep turns the display and its charge pump off, but the display RAM is powered by the
+    /// logic supply VDD rather than the charge pump, so it keeps the flushed image. A later
+    /// [`wake`](Sh1106::wake) shows the same content without flushing again. The content is only
+    /// lost if VDD itself is removed, or the display is reset; initialise it again and call
+    /// [`mark_all_dirty`](Sh1106::mark_all_dirty) before the next flush in that case.
+    ///
+    /// If the flush fails, the display isn't put to sleep, so the error can be handled while the
+    /// display still shows the previous image.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.flush_and_power_down().unwrap();
+    /// display.wake().unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // The pixel is flushed once, and waking only turns the charge pump and display on
+    /// assert_eq!(
+    ///     interface.commands(),
+    ///     &[0xB0, 0x02, 0x10, 0xAE, 0xAD, 0x8A, 0xAD, 0x8B, 0xAF]
+    /// );
+    /// assert_eq!(interface.data(), &[0x01]);
+    /// ```
+    pub async fn flush_and_power_down(&mut self) -> Result<(), Error> {
+        self.flush().await?;
+
+        self.sleep().await
+    }
+
+    /// Check whether the framebuffer has changed since the last [`flush`](Sh1106::flush).
+    ///
+    /// This is `true` after drawing, clearing or scrolling the framebuffer, calling
+    /// [`buffer_mut`](Sh1106::buffer_mut) or changing the rotation, and `false` after a
+    /// successful flush. Render loops can use it to skip flushing, although `flush` itself also
+    /// sends nothing when the framebuffer is unchanged.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// assert!(!display.is_dirty());
+    ///
+    /// display.set_pixel(1, 2, true);
+    /// assert!(display.is_dirty());
+    ///
+    /// display.flush().unwrap();
+    /// assert!(!display.is_dirty());
+    ///
+    /// display.clear_buffer();
+    /// assert!(display.is_dirty());
+    /// ```
+    pub fn is_dirty(&self) -> bool {
+        self.mode.is_dirty() || self.mode.flushed_rotation != self.rotation
+    }
+
+    /// Write out only the part of the framebuffer inside `area` to the display.
+    ///
+    /// The area is clipped to the display. Only the pages and columns which intersect it are
+    /// sent, which is much faster than a full [`flush`](Sh1106::flush) when redrawing a small
+    /// element at a high frame rate. The changed area tracked for `flush` is not modified.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use embedded_graphics::{prelude::*, primitives::Rectangle};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(5, 12, true);
+    /// display
+    ///     .flush_region(Rectangle::new(Point::new(4, 10), Size::new(3, 8)))
+    ///     .unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // Rows 10 to 17 span pages 1 and 2. Columns 4 to 6 start at display RAM column 6.
+    /// assert_eq!(interface.commands(), &[0xB1, 0x06, 0x10, 0xB2, 0x06, 0x10]);
+    /// assert_eq!(interface.data(), &[0x00, 0b0001_0000, 0x00, 0x00, 0x00, 0x00]);
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub async fn flush_region(&mut self, area: Rectangle) -> Result<(), Error> {
+        let area = area.intersection(&self.bounding_box());
+
+        let bottom_right = match area.bottom_right() {
+            Some(bottom_right) => bottom_right,
+            // Nothing to do if the area doesn't intersect the display
+            None => return Ok(()),
+        };
+
+        self.flush_area(
+            (area.top_left.x as u8, area.top_left.y as u8),
+            (bottom_right.x as u8, bottom_right.y as u8),
+            usize::MAX,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Write out the given pages of the framebuffer to the display.
+    ///
+    /// Each listed page of the display RAM, a row 8 pixels high, is sent in full width, starting
+    /// at the column offset of the display, which is simpler than
+    /// [`flush_region`](Sh1106::flush_region) for layouts aligned to pages, such as a status bar
+    /// in page 0. Pages past the bottom of the display are ignored. The pages are in display RAM
+    /// order, so for `Rotate90` and `Rotate270` they are columns of the rotated image. The changed
+    /// area tracked for [`flush`](Sh1106::flush) is not modified.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.flush_pages(&[0, 8, 200]).unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // Page 0 from display RAM column 2, the other pages don't exist
+    /// assert_eq!(interface.commands(), &[0xB0, 0x02, 0x10]);
+    /// assert_eq!(interface.data().len(), 128);
+    /// assert_eq!(interface.data()[0], 0x01);
+    /// ```
+    pub async fn flush_pages(&mut self, pages: &[u8]) -> Result<(), Error> {
+        let width = SIZE::WIDTH as usize;
+        let column_offset = self.ram_column_offset()?;
+
+        for &page in pages {
+            let range = page as usize * width..(page as usize + 1) * width;
+
+            let data = match self.mode.buffer.as_ref().get(range.clone()) {
+                Some(data) => data,
+                // The page is past the bottom of the display
+                None => continue,
+            };
+
+            Self::send_run(
+                &mut self.interface,
+                self.addressing,
+                page,
+                column_offset.into(),
+                data,
+                usize::MAX,
+            )
+            .await
+            .map_err(|error| error.in_stage(Error::Flush))?;
+
+            // Keep the copy of the display RAM in sync, so diffing flushes don't skip these bytes
+            if let Some(shadow) = self.mode.shadow.as_shadow_mut() {
+                shadow[range.clone()].copy_from_slice(&self.mode.buffer.as_ref()[range]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write out the part of the framebuffer between the given logical corners, inclusive, in
+    /// transfers of at most `chunk_size` bytes.
+    async fn flush_area(
+        &mut self,
+        upper_left: (u8, u8),
+        lower_right: (u8, u8),
+        chunk_size: usize,
+    ) -> Result<FlushStats, Error> {
+        let (width, height) = self.dimensions();
+
+        // Determine which bytes need to be sent
+        let (disp_min_x, disp_min_y) = upper_left;
+
+        let (disp_max_x, disp_max_y) = match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (
+                (lower_right.0 + 1).min(width),
+                (lower_right.1 | 7).min(height),
+            ),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (
+                (lower_right.0 | 7).min(width),
+                (lower_right.1 + 1).min(height),
+            ),
+        };
+
+        let column_offset = self.ram_column_offset()?;
+
+        // Display RAM page width and corners, which are swapped when rotated by 90 or 270 degrees
+        let (disp_width, upper_left, lower_right) = match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (
+                width as usize,
+                (disp_min_x, disp_min_y),
+                (disp_max_x, disp_max_y),
+            ),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (
+                height as usize,
+                (disp_min_y, disp_min_x),
+                (disp_max_y, disp_max_x),
+            ),
+        };
+
+        let result = match self.mode.shadow.as_shadow_mut() {
+            Some(shadow) => {
+                Self::flush_buffer_diff(
+                    &mut self.interface,
+                    self.addressing,
+                    self.mode.buffer.as_ref(),
+                    shadow,
+                    self.mode.shadow_valid,
+                    disp_width,
+                    upper_left,
+                    lower_right,
+                    column_offset,
+                    chunk_size,
+                    self.mode.flush_order,
+                )
+                .await
+            }
+            None => {
+                Self::flush_buffer_chunks(
+                    &mut self.interface,
+                    self.addressing,
+                    self.mode.buffer.as_ref(),
+                    disp_width,
+                    upper_left,
+                    lower_right,
+                    column_offset,
+                    chunk_size,
+                    self.mode.flush_order,
+                )
+                .await
+            }
+        };
+
+        result.map_err(|error| error.in_stage(Error::Flush))
+    }
+
+    /// Send the bytes of the framebuffer between the given display RAM corners which differ from
+    /// `shadow`, and update `shadow` to match. If `shadow` doesn't match the display RAM, every
+    /// byte is sent.
+    ///
+    /// Each run of changed bytes in a page is sent in transfers of up to `chunk_size` bytes,
+    /// preceded by its address.
+    #[allow(clippy::too_many_arguments)]
+    async fn flush_buffer_diff(
+        interface: &mut DI,
+        addressing: &dyn AddressingStrategy,
+        buffer: &[u8],
+        shadow: &mut [u8],
+        shadow_valid: bool,
+        disp_width: usize,
+        upper_left: (u8, u8),
+        lower_right: (u8, u8),
+        column_offset: u8,
+        chunk_size: usize,
+        order: FlushOrder,
+    ) -> Result<FlushStats, Error> {
+        let (first_column, last_column) = (upper_left.0 as usize, lower_right.0 as usize);
+        let mut stats = FlushStats::default();
+
+        for page in order.pages(upper_left.1 / 8, lower_right.1 / 8) {
+            let start = page as usize * disp_width;
+            let new = &buffer[start + first_column..start + last_column];
+            let old = &mut shadow[start + first_column..start + last_column];
+
+            let mut column = 0;
+            let bytes_sent = stats.bytes_sent;
+
+            while column < new.len() {
+                // Skip bytes the display already shows
+                if shadow_valid && new[column] == old[column] {
+                    column += 1;
+                    continue;
+                }
+
+                let run_start = column;
+
+                while column < new.len() && (!shadow_valid || new[column] != old[column]) {
+                    column += 1;
+                }
+
+                Self::send_run(
+                    interface,
+                    addressing,
+                    page,
+                    usize::from(column_offset) + first_column + run_start,
+                    &new[run_start..column],
+                    chunk_size,
+                )
+                .await?;
+
+                old[run_start..column].copy_from_slice(&new[run_start..column]);
+                stats.bytes_sent += column - run_start;
+            }
+
+            if stats.bytes_sent > bytes_sent {
+                stats.pages_sent += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Turn a pixel on or off. A non-zero `value` is treated as on, `0` as off. If the X and Y
+    /// coordinates are out of the bounds of the display, this method call is a noop.
+    ///
+    /// The coordinates are in the rotated coordinate system returned by
+    /// [`dimensions`](Sh1106::dimensions). The framebuffer always uses the page layout of the
+    /// display RAM, so for `Rotate0` and `Rotate180` the pixel is stored in page `y / 8`, column
+    /// `x`, bit `y % 8`. For `Rotate90` and `Rotate270` the axes are swapped and the pixel is
+    /// stored in page `x / 8`, column `y`, bit `x % 8`. The hardware segment remap and COM scan
+    /// direction set by [`set_rotation`](Sh1106::set_rotation) take care of the rest.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// for (rotation, page, column, byte) in [
+    ///     (DisplayRotation::Rotate0, 0xB2, 3, 0b0000_0010),
+    ///     (DisplayRotation::Rotate90, 0xB0, 17, 0b0000_1000),
+    ///     (DisplayRotation::Rotate180, 0xB2, 3, 0b0000_0010),
+    ///     (DisplayRotation::Rotate270, 0xB0, 17, 0b0000_1000),
+    /// ] {
+    ///     let mut display = Sh1106::new(MockInterface::new(), DisplaySize128x64, rotation)
+    ///         .into_buffered_graphics_mode();
+    ///
+    ///     display.set_pixel(3, 17, true);
+    ///     display.flush().unwrap();
+    ///
+    ///     let interface = display.release();
+    ///
+    ///     // The 128x64 panel starts at display RAM column 2 in every rotation
+    ///     let column: u8 = column + 2;
+    ///     assert_eq!(interface.commands(), &[page, column & 0xF, 0x10 | column >> 4]);
+    ///     assert_eq!(interface.data(), &[byte]);
+    /// }
+    ///
+    /// // Pixels outside the display are ignored
+    /// # let interface = MockInterface::new();
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(128, 0, true);
+    /// display.set_pixel(0, 64, true);
+    /// display.flush().unwrap();
+    ///
+    /// assert!(display.release().commands().is_empty());
+    /// ```
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: bool) {
+        let (width, height) = self.dimensions();
+
+        // Ignore out of bounds pixels instead of wrapping them into the next page
+        if x >= u32::from(width) || y >= u32::from(height) {
+            return;
+        }
+
+        let value = self.pixel_value(value) as u8;
+        let (idx, bit) = Self::pixel_position(self.rotation, x, y);
+
+        // The bounds check above keeps the index inside the buffer, so a missing byte means the
+        // buffer doesn't match the display size
+        debug_assert!(idx < self.mode.buffer.as_ref().len());
+
+        if let Some(byte) = self.mode.buffer.as_mut().get_mut(idx) {
+            // Set pixel value in byte
+            // Ref this comment https://stackoverflow.com/questions/47981/how-do-you-set-clear-and-toggle-a-single-bit#comment46654671_47990
+            *byte = *byte & !(1 << bit) | (value << bit);
+
+            // Keep track of max and min values
+            self.mode.mark_dirty(x as u8, y as u8);
+        }
+    }
+
+    /// Get the framebuffer byte index and bit of the pixel at `x`, `y` in the rotated coordinate
+    /// system
+    fn pixel_position(rotation: DisplayRotation, x: u32, y: u32) -> (usize, u32) {
+        match rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                let idx = ((y as usize) / 8 * SIZE::WIDTH as usize) + (x as usize);
+                let bit = y % 8;
+
+                (idx, bit)
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                let idx = ((x as usize) / 8 * SIZE::WIDTH as usize) + (y as usize);
+                let bit = x % 8;
+
+                (idx, bit)
+            }
+        }
+    }
+
+    /// Turn the pixels in the columns `x` of row `y` on or off, in the rotated coordinate system
+    /// of [`set_pixel`](Sh1106::set_pixel).
+    ///
+    /// Pixels outside the display are skipped. Each column of a line is a single masked byte
+    /// write, which is much faster than setting the pixels one by one, so this is a good building
+    /// block for grids and borders.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // Row 9 is bit 1 of page 1
+    /// display.hline(2..5, 9, true);
+    ///
+    /// assert_eq!(&display.buffer()[128..134], &[0x00, 0x00, 0x02, 0x02, 0x02, 0x00]);
+    ///
+    /// // The line is clipped to the display
+    /// display.hline(120..200, 63, true);
+    ///
+    /// assert_eq!(display.buffer()[7 * 128 + 127], 0x80);
+    /// ```
+    pub fn hline(&mut self, x: Range<u32>, y: u32, on: bool) {
+        let (width, height) = self.dimensions();
+        let end = x.end.min(u32::from(width));
+
+        if y < u32::from(height) && x.start < end {
+            let value = self.pixel_value(on);
+
+            self.fill_area((x.start as u8, y as u8), ((end - 1) as u8, y as u8), value);
+        }
+    }
+
+    /// Turn the pixels in the rows `y` of column `x` on or off, in the rotated coordinate system
+    /// of [`set_pixel`](Sh1106::set_pixel).
+    ///
+    /// Pixels outside the display are skipped. The line is written as a bit span of one byte in
+    /// each page it crosses, which is much faster than setting the pixels one by one.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // Rows 6 to 17 cross from page 0 through page 1 into page 2
+    /// display.vline(3, 6..18, true);
+    ///
+    /// let buffer = display.buffer();
+    /// assert_eq!((buffer[3], buffer[128 + 3], buffer[256 + 3]), (0xC0, 0xFF, 0x03));
+    /// assert_eq!(buffer.iter().filter(|byte| **byte != 0).count(), 3);
+    ///
+    /// // Turning part of the line off only clears those bits
+    /// display.vline(3, 7..10, false);
+    ///
+    /// let buffer = display.buffer();
+    /// assert_eq!((buffer[3], buffer[128 + 3]), (0x40, 0xFC));
+    /// ```
+    pub fn vline(&mut self, x: u32, y: Range<u32>, on: bool) {
+        let (width, height) = self.dimensions();
+        let end = y.end.min(u32::from(height));
+
+        if x < u32::from(width) && y.start < end {
+            let value = self.pixel_value(on);
+
+            self.fill_area((x as u8, y.start as u8), (x as u8, (end - 1) as u8), value);
+        }
+    }
+
+    /// Get whether the bit of the pixel at `x`, `y` is set in the framebuffer, in the rotated
+    /// coordinate system of [`set_pixel`](Sh1106::set_pixel).
+    fn pixel_is_set(&self, x: u32, y: u32) -> bool {
+        let (idx, bit) = Self::pixel_position(self.rotation, x, y);
+
+        self.mode
+            .buffer
+            .as_ref()
+            .get(idx)
+            .is_some_and(|byte| byte & (1 << bit) != 0)
+    }
+
+    /// Write the framebuffer to `out` as a plain PBM (P1) image, for example to view it on a PC
+    /// while developing a layout without the hardware at hand.
+    ///
+    /// The image has the rotated size given by [`dimensions`](Sh1106::dimensions) and is
+    /// written row by row, so it shows the framebuffer the way the panel does after a
+    /// [`flush`](Sh1106::flush). Pixels which are on are written as white (`0`) and pixels which
+    /// are off as black (`1`), so the image looks like the panel. The plain format is used because
+    /// `out` only takes text. Most image viewers open it directly, and tools such as ImageMagick or
+    /// netpbm can convert it to PNG. Use [`export_pbm_raw`](Sh1106::export_pbm_raw) for the 8
+    /// times smaller binary P4 format.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(StubInterface, DisplaySize128x32, DisplayRotation::Rotate90)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.set_pixel(31, 1, true);
+    ///
+    /// let mut pbm = String::new();
+    /// display.export_pbm(&mut pbm).unwrap();
+    ///
+    /// let mut lines = pbm.lines();
+    /// assert_eq!(lines.next(), Some("P1"));
+    /// assert_eq!(lines.next(), Some("32 128"));
+    /// assert_eq!(lines.next(), Some(format!("0{}", "1".repeat(31)).as_str()));
+    /// assert_eq!(lines.next(), Some(format!("{}0", "1".repeat(31)).as_str()));
+    /// assert_eq!(lines.filter(|row| *row == "1".repeat(32)).count(), 126);
+    /// ```
+    pub fn export_pbm(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        let (width, height) = self.dimensions();
+
+        writeln!(out, "P1")?;
+        writeln!(out, "{} {}", width, height)?;
+
+        for y in 0..height {
+            for x in 0..width {
+                let on = self.pixel_is_set(x.into(), y.into());
+
+                out.write_char(if on { '0' } else { '1' })?;
+            }
+
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the framebuffer as a binary PBM (P4) image, passing each byte to `out`.
+    ///
+    /// This is the same image as [`export_pbm`](Sh1106::export_pbm), with 8 pixels packed into
+    /// each byte, most significant bit first, and every row padded to a whole byte. `out` can
+    /// write the bytes straight to a serial port, or collect them into a buffer.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(StubInterface, DisplaySize128x32, DisplayRotation::Rotate90)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.set_pixel(31, 1, true);
+    ///
+    /// let mut pbm = Vec::new();
+    /// display.export_pbm_raw(|byte| pbm.push(byte));
+    ///
+    /// let (header, rows) = pbm.split_at(10);
+    /// assert_eq!(header, b"P4\n32 128\n");
+    /// // 4 bytes for each of the 128 rows of 32 pixels
+    /// assert_eq!(rows.len(), 128 * 4);
+    /// assert_eq!(&rows[..8], &[0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE]);
+    /// assert!(rows[8..].iter().all(|byte| *byte == 0xFF));
+    /// ```
+    pub fn export_pbm_raw(&self, mut out: impl FnMut(u8)) {
+        use core::fmt::Write;
+
+        // The header is text, so format it through a writer passing its bytes on
+        struct Bytes<F>(F);
+
+        impl<F: FnMut(u8)> Write for Bytes<F> {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                s.bytes().for_each(&mut self.0);
+
+                Ok(())
+            }
+        }
+
+        let (width, height) = self.dimensions();
+
+        // Writing to the closure can't fail
+        let _ = write!(Bytes(&mut out), "P4\n{} {}\n", width, height);
+
+        for y in 0..height {
+            let mut byte = 0;
+
+            for x in 0..width {
+                // Set bits are black, so pixels which are off
+                if !self.pixel_is_set(x.into(), y.into()) {
+                    byte |= 0x80 >> (x % 8);
+                }
+
+                if x % 8 == 7 || x == width - 1 {
+                    out(byte);
+                    byte = 0;
+                }
+            }
+        }
+    }
+
+    /// Invert every pixel of the framebuffer inside `area`. You need to call `disp.flush()` for
+    /// any effect on the screen.
+    ///
+    /// The area is clipped to the display. This is a cheap way to highlight an element such as a
+    /// selected menu item, and inverting the same area again restores it.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use embedded_graphics::{prelude::*, primitives::Rectangle};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(3, 5, true);
+    ///
+    /// // Rows 4 to 13 are bits 4 to 7 of page 0 and bits 0 to 5 of page 1
+    /// display.invert_region(Rectangle::new(Point::new(2, 4), Size::new(10, 10)));
+    ///
+    /// let buffer = display.buffer();
+    /// assert_eq!(&buffer[0..4], &[0x00, 0x00, 0xF0, 0xD0]);
+    /// assert_eq!(&buffer[11..13], &[0xF0, 0x00]);
+    /// assert_eq!(&buffer[128..132], &[0x00, 0x00, 0x3F, 0x3F]);
+    /// assert_eq!(&buffer[139..141], &[0x3F, 0x00]);
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn invert_region(&mut self, area: Rectangle) {
+        let area = area.intersection(&self.bounding_box());
+
+        if let Some(bottom_right) = area.bottom_right() {
+            self.modify_area(
+                (area.top_left.x as u8, area.top_left.y as u8),
+                (bottom_right.x as u8, bottom_right.y as u8),
+                |byte, mask| byte ^ mask,
+            );
+        }
+    }
+
+    /// Turn off every pixel of the framebuffer inside `area`, for example to erase stale text
+    /// before redrawing it. You need to call `disp.flush()` for any effect on the screen.
+    ///
+    /// The area is clipped to the display. Like [`clear_buffer`](Sh1106::clear_buffer), this
+    /// zeroes the framebuffer regardless of the [`ColorMode`]. Pages covered completely are
+    /// zeroed a byte at a time, and only the covered bits of the pages at the top and bottom edge
+    /// are cleared, which is much faster than drawing a filled rectangle.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use embedded_graphics::{prelude::*, primitives::Rectangle};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.clear_pattern(0xFF);
+    ///
+    /// // Rows 6 to 9 are bits 6 and 7 of page 0 and bits 0 and 1 of page 1
+    /// display.clear_region(Rectangle::new(Point::new(2, 6), Size::new(3, 4)));
+    ///
+    /// let buffer = display.buffer();
+    /// assert_eq!(&buffer[1..6], &[0xFF, 0x3F, 0x3F, 0x3F, 0xFF]);
+    /// assert_eq!(&buffer[129..134], &[0xFF, 0xFC, 0xFC, 0xFC, 0xFF]);
+    /// assert_eq!(buffer.iter().filter(|byte| **byte != 0xFF).count(), 6);
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn clear_region(&mut self, area: Rectangle) {
+        self.fill_rectangle(area, false);
+    }
+
+    /// Iterate over every pixel of the framebuffer, row by row, as its position in the rotated
+    /// coordinate system and its color.
+    ///
+    /// This is the inverse of drawing: the positions use the same rotation, and the colors the
+    /// same [`ColorMode`], so reading back what was drawn gives the same pixels. It is useful for
+    /// comparing the framebuffer in tests, or for effects which read back the image.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x32, DisplayRotation::Rotate90)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// let drawn = [
+    ///     Pixel(Point::new(3, 100), BinaryColor::On),
+    ///     Pixel(Point::new(31, 0), BinaryColor::On),
+    /// ];
+    /// display.draw_iter(drawn).unwrap();
+    ///
+    /// assert_eq!(display.pixels().count(), 32 * 128);
+    ///
+    /// let lit = display
+    ///     .pixels()
+    ///     .filter(|(_, color)| color.is_on())
+    ///     .map(|(point, color)| Pixel(point, color));
+    ///
+    /// // Rows come first, so the pixel at the top is read back first
+    /// assert!(lit.eq(drawn.into_iter().rev()));
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn pixels(&self) -> impl Iterator<Item = (Point, BinaryColor)> + '_ {
+        let (width, height) = self.dimensions();
+
+        (0..u32::from(height)).flat_map(move |y| {
+            (0..u32::from(width)).map(move |x| {
+                let on = self.pixel_is_set(x, y);
+
+                (
+                    Point::new(x as i32, y as i32),
+                    BinaryColor::from(self.pixel_value(on)),
+                )
+            })
+        })
+    }
+
+    /// Draw a horizontal progress bar into `area`: a 1 pixel border, with the left `fraction` of
+    /// the inside filled and the rest cleared. You need to call `disp.flush()` for any effect on
+    /// the screen.
+    ///
+    /// `fraction` is clamped to `0.0..=1.0`, and NaN is treated as 0. The filled width is rounded
+    /// to the nearest column. The bar is clipped to the display and drawn with whole byte writes,
+    /// which is much faster than drawing it pixel by pixel.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use embedded_graphics::{prelude::*, primitives::Rectangle};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // 20 columns inside the border
+    /// let bar = Rectangle::new(Point::zero(), Size::new(22, 8));
+    ///
+    /// for (fraction, columns) in [(0.0, 0), (0.5, 10), (1.0, 20), (2.0, 20)] {
+    ///     display.draw_progress_bar(bar, fraction);
+    ///
+    ///     let inside = &display.buffer()[1..21];
+    ///
+    ///     // Filled columns are solid, the others only have the top and bottom border
+    ///     assert_eq!(inside.iter().filter(|byte| **byte == 0xFF).count(), columns);
+    ///     assert_eq!(inside.iter().filter(|byte| **byte == 0x81).count(), 20 - columns);
+    ///     assert_eq!((display.buffer()[0], display.buffer()[21]), (0xFF, 0xFF));
+    /// }
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn draw_progress_bar(&mut self, area: Rectangle, fraction: f32) {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+
+        let inside = Rectangle::new(
+            area.top_left + Point::new(1, 1),
+            Size::new(
+                area.size.width.saturating_sub(2),
+                area.size.height.saturating_sub(2),
+            ),
+        );
+        let filled = (inside.size.width as f32 * fraction + 0.5) as u32;
+        let on = self.pixel_value(true);
+
+        // Fill the whole bar, then clear the part of the inside which isn't filled
+        self.fill_rectangle(area, on);
+        self.fill_rectangle(
+            Rectangle::new(
+                inside.top_left + Point::new(filled as i32, 0),
+                Size::new(inside.size.width - filled, inside.size.height),
+            ),
+            !on,
+        );
+    }
+
+    /// Set every pixel of `area` which is inside the display.
+    ///
+    /// `value` is the framebuffer bit, see [`pixel_value`](Sh1106::pixel_value).
+    #[cfg(feature = "graphics")]
+    pub(crate) fn fill_rectangle(&mut self, area: Rectangle, value: bool) {
+        let area = area.intersection(&self.bounding_box());
+
+        if let Some(bottom_right) = area.bottom_right() {
+            self.fill_area(
+                (area.top_left.x as u8, area.top_left.y as u8),
+                (bottom_right.x as u8, bottom_right.y as u8),
+                value,
+            );
+        }
+    }
+
+    /// Fill the rows of `area` given by `rows`, a first row, number of rows and value, of
+    /// [`fill_contiguous`](DrawTarget::fill_contiguous).
+    #[cfg(feature = "graphics")]
+    fn fill_rows(&mut self, area: &Rectangle, rows: Option<(i32, u32, bool)>) {
+        if let Some((y, height, value)) = rows {
+            let rows = Rectangle::new(
+                Point::new(area.top_left.x, y),
+                Size::new(area.size.width, height),
+            );
+
+            self.fill_rectangle(rows, value);
+        }
+    }
+
+    /// Fill the columns `x` of row `y`, skipping those outside the display.
+    #[cfg(feature = "graphics")]
+    fn fill_run(&mut self, x: Range<i32>, y: i32, value: bool) {
+        let run = Rectangle::new(Point::new(x.start, y), Size::new(x.len() as u32, 1));
+
+        self.fill_rectangle(run, value);
+    }
+
+    /// Set every pixel in the inclusive logical area from `upper_left` to `lower_right`.
+    fn fill_area(&mut self, upper_left: (u8, u8), lower_right: (u8, u8), value: bool) {
+        if value {
+            self.modify_area(upper_left, lower_right, |byte, mask| byte | mask);
+        } else {
+            self.modify_area(upper_left, lower_right, |byte, mask| byte & !mask);
+        }
+    }
+
+    /// Replace every framebuffer byte in the inclusive logical area from `upper_left` to
+    /// `lower_right` with `op(byte, mask)`, where `mask` has the bits inside the area set.
+    ///
+    /// The area must be within the display. The mask is `0xff` for the pages covered completely,
+    /// and only has the covered bits set for the partially covered pages above and below.
+    fn modify_area(
+        &mut self,
+        upper_left: (u8, u8),
+        lower_right: (u8, u8),
+        op: impl Fn(u8, u8) -> u8,
+    ) {
+        // Work in buffer columns and rows, which are swapped by a 90 or 270 degree rotation
+        let ((min_col, min_row), (max_col, max_row)) = match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (upper_left, lower_right),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                ((upper_left.1, upper_left.0), (lower_right.1, lower_right.0))
+            }
+        };
+
+        let width = SIZE::WIDTH as usize;
+
+        for page in min_row / 8..=max_row / 8 {
+            let top = if page == min_row / 8 { min_row % 8 } else { 0 };
+            let bottom = if page == max_row / 8 { max_row % 8 } else { 7 };
+            let mask = (0xffu8 >> (7 - bottom + top)) << top;
+
+            let start = page as usize * width + min_col as usize;
+            let end = page as usize * width + max_col as usize + 1;
+
+            if let Some(bytes) = self.mode.buffer.as_mut().get_mut(start..end) {
+                for byte in bytes {
+                    *byte = op(*byte, mask);
+                }
+            }
+        }
+
+        self.mode.mark_dirty(upper_left.0, upper_left.1);
+        self.mode.mark_dirty(lower_right.0, lower_right.1);
+    }
+
+    /// Draw a packed 1 bit per pixel bitmap into the framebuffer, with its top left corner at
+    /// `top_left`.
+    ///
+    /// Each row of the bitmap is `width` pixels wide, stored most significant bit first and padded
+    /// to a whole number of bytes. Set bits turn pixels on and clear bits turn them off. Any part
+    /// of the bitmap outside the display is skipped.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use embedded_graphics::prelude::*;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // An 8x8 square outline
+    /// let glyph = [0xFF, 0x81, 0x81, 0x81, 0x81, 0x81, 0x81, 0xFF];
+    ///
+    /// // Rows 3 to 10 span pages 0 and 1
+    /// display.draw_raw(Point::new(0, 3), 8, &glyph);
+    ///
+    /// let buffer = display.buffer();
+    /// assert_eq!(&buffer[0..8], &[0xF8, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0xF8]);
+    /// assert_eq!(&buffer[128..136], &[0x07, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x07]);
+    ///
+    /// // Only the right half fits on the display
+    /// display.clear_buffer();
+    /// display.draw_raw(Point::new(-4, 0), 8, &glyph);
+    ///
+    /// assert_eq!(&display.buffer()[0..5], &[0x81, 0x81, 0x81, 0xFF, 0x00]);
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn draw_raw(&mut self, top_left: Point, width: u32, data: &[u8]) {
+        let stride = width.div_ceil(8) as usize;
+
+        if stride == 0 {
+            return;
+        }
+
+        for (row, bytes) in data.chunks_exact(stride).enumerate() {
+            let y = top_left.y + row as i32;
+
+            for column in 0..width {
+                let x = top_left.x + column as i32;
+
+                if x < 0 || y < 0 {
+                    continue;
+                }
+
+                let bit = bytes[column as usize / 8] & (0x80 >> (column % 8));
+
+                self.set_pixel(x as u32, y as u32, bit != 0);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "graphics")]
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            Command(async = "CommandAsync"),
+            DisplaySize(async = "DisplaySizeAsync"),
+            RmwInterface(async = "RmwInterfaceAsync"),
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync")
+        )
+    )
+)]
+impl<DI, SIZE, SHADOW, BUF> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
+where
+    DI: RmwInterface,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Write out only the pixels of the framebuffer inside `area` to the display, keeping the
+    /// other pixels of the display RAM as they are.
+    ///
+    /// Unlike [`flush_region`](Sh1106::flush_region), which always sends whole 8px high pages,
+    /// this uses the read-modify-write mode of the controller to read every display RAM byte in
+    /// the area, replace only the bits inside it and write the result back. This allows
+    /// updating a small element such as a blinking cursor without having to keep the pixels
+    /// around it in the framebuffer. The changed area tracked for [`flush`](Sh1106::flush) is not
+    /// modified.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let mut interface = MockInterface::new();
+    /// use embedded_graphics::{prelude::*, primitives::Rectangle};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// // Every pixel of the display RAM is currently on
+    /// interface.set_read_value(0xFF);
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(5, 12, true);
+    /// display
+    ///     .flush_rmw(Rectangle::new(Point::new(4, 10), Size::new(3, 4)))
+    ///     .unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // Rows 10 to 13 are bits 2 to 5 of page 1, so only those bits are replaced
+    /// assert_eq!(interface.commands(), &[0xB1, 0x06, 0x10, 0xE0, 0xEE]);
+    /// assert_eq!(interface.data(), &[0b1100_0011, 0b1101_0011, 0b1100_0011]);
+    /// ```
+    pub async fn flush_rmw(&mut self, area: Rectangle) -> Result<(), Error> {
+        self.flush_rmw_area(area)
+            .await
+            .map_err(|error| error.in_stage(Error::Flush))
+    }
+
+    async fn flush_rmw_area(&mut self, area: Rectangle) -> Result<(), Error> {
+        let area = area.intersection(&self.bounding_box());
+
+        let bottom_right = match area.bottom_right() {
+            Some(bottom_right) => bottom_right,
+            // Nothing to do if the area doesn't intersect the display
+            None => return Ok(()),
+        };
+
+        let (width, height) = self.dimensions();
+        let (left, top) = (area.top_left.x as u8, area.top_left.y as u8);
+        let (right, bottom) = (bottom_right.x as u8, bottom_right.y as u8);
+
+        // Columns and rows of the display RAM, which are swapped when rotated by 90 or 270 degrees
+        let (stride, columns, rows) = match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                (width, (left, right), (top, bottom))
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                (height, (top, bottom), (left, right))
+            }
+        };
+
+        for page in rows.0 / 8..=rows.1 / 8 {
+            let first_bit = rows.0.max(page * 8) - page * 8;
+            let last_bit = rows.1.min(page * 8 + 7) - page * 8;
+            let mask = (0xFF << first_bit) & (0xFF >> (7 - last_bit));
+
+            self.set_row(page * 8).await?;
+            self.set_column(columns.0).await?;
+            Command::ReadModifyWriteStart
+                .send(&mut self.interface)
+                .await?;
+
+            // The first read after setting the address returns invalid data
+            let mut read = [0];
+            self.interface.read_data(&mut read).await?;
+
+            for column in columns.0..=columns.1 {
+                let index = page as usize * stride as usize + column as usize;
+
+                // The column address only increments on writes in read-modify-write mode
+                self.interface.read_data(&mut read).await?;
+                let byte = (read[0] & !mask) | (self.mode.buffer.as_ref()[index] & mask);
+
+                self.draw(&[byte]).await?;
+            }
+
+            Command::ReadModifyWriteEnd
+                .send(&mut self.interface)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, SIZE, SHADOW, BUF> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Flush the changed area of the display after every embedded-graphics draw call.
+    ///
+    /// This is off by default. When enabled, there is no need to call
+    /// [`flush`](Sh1106::flush) after drawing, which is convenient for prototyping and for UIs
+    /// that update rarely.
+    ///
+    /// Note that every drawn primitive then results in its own transfer to the display. Drawing
+    /// a screen made of many small primitives (e.g. a line of text, where every character is a
+    /// separate draw call) is much slower than drawing everything and flushing once, and on a
+    /// 400kHz I2C bus can easily take tens of milliseconds. Errors from the automatic flush are
+    /// returned from the draw call.
+    ///
+    /// This is only available for the blocking driver, as embedded-graphics draw calls cannot
+    /// wait for an asynchronous transfer.
+    pub fn set_auto_flush(&mut self, enabled: bool) {
+        self.mode.auto_flush = enabled;
+    }
+
+    #[cfg(feature = "graphics")]
+    fn flush_if_auto(&mut self) -> Result<(), Error> {
+        if self.mode.auto_flush {
+            self.flush()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(feature = "graphics", feature = "async"))]
+impl<DI, SIZE, SHADOW, BUF> Sh1106Async<DI, SIZE, BufferedGraphicsModeAsync<SIZE, SHADOW, BUF>>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySizeAsync,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    fn flush_if_auto(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::Size,
+    geometry::{Dimensions, OriginDimensions, Point},
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+use super::DisplayConfig;
+#[cfg(feature = "async")]
+use super::DisplayConfigAsync;
+
+#[cfg(feature = "graphics")]
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand")
+        )
+    )
+)]
+impl<DI, SIZE, SHADOW, BUF> DrawTarget for Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    type Color = BinaryColor;
+    type Error = Error;
+
+    /// Draw individual pixels, skipping those outside the display.
+    ///
+    /// This gives the same result as calling [`set_pixel`](Sh1106::set_pixel) for each pixel,
+    /// but the rotation and the changed area are only handled once per call, and the page offset
+    /// is reused while consecutive pixels stay in the same page, as they do for text and
+    /// horizontal lines.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let pixels = [(3, 7), (4, 7), (5, 8), (6, 8), (-1, 0), (200, 3)];
+    ///
+    /// for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90] {
+    ///     let mut drawn = Sh1106::new(StubInterface, DisplaySize128x64, rotation)
+    ///         .into_buffered_graphics_mode();
+    ///     let mut set = Sh1106::new(StubInterface, DisplaySize128x64, rotation)
+    ///         .into_buffered_graphics_mode();
+    ///
+    ///     drawn
+    ///         .draw_iter(
+    ///             pixels
+    ///                 .iter()
+    ///                 .map(|&(x, y)| Pixel(Point::new(x, y), BinaryColor::On)),
+    ///         )
+    ///         .unwrap();
+    ///
+    ///     for &(x, y) in pixels.iter().filter(|(x, y)| *x >= 0 && *y >= 0) {
+    ///         set.set_pixel(x as u32, y as u32, true);
+    ///     }
+    ///
+    ///     assert_eq!(drawn.buffer(), set.buffer());
+    /// }
+    /// ```
+    ///
+    /// Like embedded-graphics requires, pixels outside the display are clipped, in every
+    /// rotation and for any coordinates, instead of wrapping into another page or panicking:
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let outside = [(-5, 0), (200, 0), (0, -1), (0, 100), (128, 63), (127, 64)];
+    ///
+    /// for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate180] {
+    ///     let mut display = Sh1106::new(StubInterface, DisplaySize128x64, rotation)
+    ///         .into_buffered_graphics_mode();
+    ///
+    ///     display
+    ///         .draw_iter(
+    ///             outside
+    ///                 .iter()
+    ///                 .map(|&(x, y)| Pixel(Point::new(x, y), BinaryColor::On)),
+    ///         )
+    ///         .unwrap();
+    ///     display.set_pixel(200, 0, true);
+    ///     display.set_pixel(0, 100, true);
+    ///
+    ///     assert!(display.buffer().iter().all(|byte| *byte == 0));
+    ///     assert!(!display.is_dirty());
+    /// }
+    /// ```
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (width, height) = self.dimensions();
+
+        // Pixels are stored by buffer column and row, which are swapped by a 90 or 270 degree
+        // rotation
+        let swap = matches!(
+            self.rotation,
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270
+        );
+
+        let inverted = self.mode.color_mode == ColorMode::Inverted;
+        let buffer = self.mode.buffer.as_mut();
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (u8::MAX, u8::MAX, 0, 0);
+
+        // Page of the previous pixel and the index of its first byte
+        let mut page = (u8::MAX, 0);
+
+        for Pixel(pos, color) in pixels {
+            if pos.x < 0 || pos.y < 0 || pos.x >= i32::from(width) || pos.y >= i32::from(height) {
+                continue;
+            }
+
+            let (x, y) = (pos.x as u8, pos.y as u8);
+            let (column, row) = if swap { (y, x) } else { (x, y) };
+
+            if row / 8 != page.0 {
+                page = (row / 8, (row / 8) as usize * SIZE::WIDTH as usize);
+            }
+
+            debug_assert!(page.1 + (column as usize) < buffer.len());
+
+            if let Some(byte) = buffer.get_mut(page.1 + column as usize) {
+                let bit = 1 << (row % 8);
+
+                if color.is_on() != inverted {
+                    *byte |= bit;
+                } else {
+                    *byte &= !bit;
+                }
+
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+
+        if min_x <= max_x {
+            self.mode.mark_dirty(min_x, min_y);
+            self.mode.mark_dirty(max_x, max_y);
+        }
+
+        self.flush_if_auto()
+    }
+
+    /// Fill a rectangle with a solid color.
+    ///
+    /// This writes whole bytes of the framebuffer at a time instead of setting each pixel.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // Rows 6 to 17 cover the bottom of page 0, all of page 1 and the top of page 2
+    /// display
+    ///     .fill_solid(&Rectangle::new(Point::new(1, 6), Size::new(2, 12)), BinaryColor::On)
+    ///     .unwrap();
+    ///
+    /// let buffer = display.buffer();
+    /// assert_eq!(&buffer[0..4], &[0x00, 0xC0, 0xC0, 0x00]);
+    /// assert_eq!(&buffer[128..132], &[0x00, 0xFF, 0xFF, 0x00]);
+    /// assert_eq!(&buffer[256..260], &[0x00, 0x03, 0x03, 0x00]);
+    ///
+    /// // Filling the whole display sets every byte
+    /// display.fill_solid(&display.bounding_box(), BinaryColor::On).unwrap();
+    /// assert!(display.buffer().iter().all(|byte| *byte == 0xFF));
+    /// ```
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_rectangle(*area, self.pixel_value(color.is_on()));
+
+        self.flush_if_auto()
+    }
+
+    /// Fill a rectangle with the colors of `colors`, in row-major order.
+    ///
+    /// Consecutive pixels of the same color in a row are filled at once, and consecutive rows of
+    /// a single color are merged into one rectangle, so an image with large areas of one color
+    /// is written a framebuffer byte at a time like [`fill_solid`](DrawTarget::fill_solid).
+    /// Pixels outside the display are skipped.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // Rows 4 to 11 of a 3px wide rectangle, with one pixel off in row 9
+    /// let area = Rectangle::new(Point::new(1, 4), Size::new(3, 8));
+    /// let colors = (0..24).map(|i| if i == 16 { BinaryColor::Off } else { BinaryColor::On });
+    ///
+    /// display.fill_contiguous(&area, colors).unwrap();
+    ///
+    /// let buffer = display.buffer();
+    /// assert_eq!(&buffer[0..5], &[0x00, 0xF0, 0xF0, 0xF0, 0x00]);
+    /// assert_eq!(&buffer[128..133], &[0x00, 0x0F, 0x0D, 0x0F, 0x00]);
+    /// ```
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let mut colors = colors.into_iter();
+
+        // First row, number of rows and value of the rows so far filled with a single color
+        let mut pending: Option<(i32, u32, bool)> = None;
+
+        for y in area.rows() {
+            // Start and value of the current run of pixels in this row
+            let mut run: Option<(i32, bool)> = None;
+            let mut end = area.top_left.x;
+            let mut uniform = true;
+
+            for color in colors.by_ref().take(area.size.width as usize) {
+                let value = self.pixel_value(color.is_on());
+
+                match run {
+                    Some((_, run_value)) if run_value == value => {}
+                    Some((start, run_value)) => {
+                        if uniform {
+                            self.fill_rows(area, pending.take());
+                            uniform = false;
+                        }
+                        self.fill_run(start..end, y, run_value);
+                        run = Some((end, value));
+                    }
+                    None => run = Some((end, value)),
+                }
+
+                end += 1;
+            }
+
+            let Some((start, value)) = run else {
+                break;
+            };
+
+            if uniform && end - area.top_left.x == area.size.width as i32 {
+                match &mut pending {
+                    Some((_, rows, pending_value)) if *pending_value == value => *rows += 1,
+                    _ => {
+                        self.fill_rows(area, pending.take());
+                        pending = Some((y, 1, value));
+                    }
+                }
+            } else {
+                self.fill_rows(area, pending.take());
+                self.fill_run(start..end, y, value);
+            }
+        }
+
+        self.fill_rows(area, pending);
+
+        self.flush_if_auto()
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_impl(self.pixel_value(color.is_on()));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+#[maybe_async_cfg::maybe(
+    sync(keep_self,),
     async(
         feature = "async",
         idents(
@@ -297,14 +2406,212 @@ where
         )
     )
 )]
-impl<DI, SIZE> OriginDimensions for Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE>>
+impl<DI, SIZE, SHADOW, BUF> OriginDimensions
+    for Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
 where
     DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
 {
+    /// Get the size of the display, taking the rotation into account.
+    ///
+    /// The size is computed from the constant size of the display and the current rotation
+    /// without touching the framebuffer, so layout code querying it repeatedly is cheap.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// use embedded_graphics::prelude::*;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(StubInterface, DisplaySize128x32, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// assert_eq!(display.size(), Size::new(128, 32));
+    ///
+    /// display.set_rotation(DisplayRotation::Rotate90).unwrap();
+    /// assert_eq!(display.size(), Size::new(32, 128));
+    /// assert_eq!(display.bounding_box().size, Size::new(32, 128));
+    /// ```
+    ///
+    /// The size matches the area drawn to at every rotation, so the bounding box can be filled
+    /// without any pixels being clipped:
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// use embedded_graphics::{
+    ///     pixelcolor::BinaryColor,
+    ///     prelude::*,
+    ///     primitives::{PrimitiveStyle, Rectangle},
+    /// };
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// for (rotation, size) in [
+    ///     (DisplayRotation::Rotate0, Size::new(72, 40)),
+    ///     (DisplayRotation::Rotate90, Size::new(40, 72)),
+    ///     (DisplayRotation::Rotate180, Size::new(72, 40)),
+    ///     (DisplayRotation::Rotate270, Size::new(40, 72)),
+    /// ] {
+    ///     let mut display = Sh1106::new(StubInterface, DisplaySize72x40, rotation)
+    ///         .into_buffered_graphics_mode();
+    ///
+    ///     let bounding_box = display.bounding_box();
+    ///     assert_eq!(bounding_box.size, size);
+    ///
+    ///     bounding_box
+    ///         .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+    ///         .draw(&mut display)
+    ///         .unwrap();
+    ///     assert!(display.buffer().iter().all(|byte| *byte == 0xFF));
+    ///
+    ///     // The bottom right pixel is the last bit of the framebuffer at every rotation
+    ///     display.clear_buffer();
+    ///     Rectangle::new(bounding_box.bottom_right().unwrap(), Size::new(1, 1))
+    ///         .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+    ///         .draw(&mut display)
+    ///         .unwrap();
+    ///     assert_eq!(display.buffer()[359], 0x80);
+    ///     assert_eq!(display.buffer().iter().filter(|byte| **byte != 0).count(), 1);
+    /// }
+    /// ```
     fn size(&self) -> Size {
         let (w, h) = self.dimensions();
 
         Size::new(w.into(), h.into())
     }
 }
+
+#[cfg(all(test, feature = "graphics"))]
+mod tests {
+    use super::*;
+    use crate::test_helpers::StubInterface;
+    use embedded_graphics_core::{
+        pixelcolor::BinaryColor,
+        prelude::{DrawTarget, Point, Size},
+        primitives::{PointsIter, Rectangle},
+    };
+
+    /// A framebuffer counting how often it's borrowed for writing.
+    ///
+    /// The framebuffer is borrowed once for each page of an area filled in one go, and once for
+    /// each pixel set on its own, so the count is the number of runs of bytes written.
+    struct CountingBuffer {
+        bytes: [u8; 128 * 64 / 8],
+        writes: usize,
+    }
+
+    impl AsRef<[u8]> for CountingBuffer {
+        fn as_ref(&self) -> &[u8] {
+            &self.bytes
+        }
+    }
+
+    impl AsMut<[u8]> for CountingBuffer {
+        fn as_mut(&mut self) -> &mut [u8] {
+            self.writes += 1;
+            &mut self.bytes
+        }
+    }
+
+    type CountingDisplay = Sh1106<
+        StubInterface,
+        DisplaySize128x64,
+        BufferedGraphicsMode<DisplaySize128x64, NoShadow, CountingBuffer>,
+    >;
+
+    fn display(rotation: DisplayRotation) -> CountingDisplay {
+        let buffer = CountingBuffer {
+            bytes: [0; 128 * 64 / 8],
+            writes: 0,
+        };
+
+        Sh1106::new(StubInterface, DisplaySize128x64, rotation)
+            .into_mode(BufferedGraphicsMode::with_buffer(rotation, buffer))
+    }
+
+    #[test]
+    fn fill_contiguous_writes_whole_pages() {
+        for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90] {
+            let mut display = display(rotation);
+            let area = display.bounding_box();
+
+            display
+                .fill_contiguous(&area, core::iter::repeat(BinaryColor::On))
+                .unwrap();
+
+            // One run of 128 bytes for each of the 8 pages
+            assert_eq!(display.mode.buffer.writes, 8);
+            assert!(display.buffer().iter().all(|byte| *byte == 0xFF));
+        }
+    }
+
+    #[test]
+    fn set_pixel_writes_every_pixel() {
+        let mut display = display(DisplayRotation::Rotate0);
+
+        for y in 0..64 {
+            for x in 0..128 {
+                display.set_pixel(x, y, true);
+            }
+        }
+
+        // The per-pixel path which fill_contiguous replaces
+        assert_eq!(display.mode.buffer.writes, 128 * 64);
+        assert!(display.buffer().iter().all(|byte| *byte == 0xFF));
+    }
+
+    #[test]
+    fn fill_contiguous_merges_rows_of_one_color() {
+        let mut display = display(DisplayRotation::Rotate0);
+        let area = display.bounding_box();
+
+        // Bands of 8 rows in alternating colors, aligned to the pages
+        let colors = (0..128 * 64).map(|i| BinaryColor::from((i / (128 * 8)) & 1 == 0));
+
+        display.fill_contiguous(&area, colors).unwrap();
+
+        assert_eq!(display.mode.buffer.writes, 8);
+        assert_eq!(display.buffer()[0], 0xFF);
+        assert_eq!(display.buffer()[128], 0x00);
+    }
+
+    #[test]
+    fn fill_contiguous_writes_runs_of_mixed_rows() {
+        let mut display = display(DisplayRotation::Rotate0);
+        let area = Rectangle::new(Point::new(-2, 3), Size::new(8, 2));
+
+        // Each row is 4 pixels on and 4 off, and the first 2 columns are off the display
+        let colors = (0..16).map(|i| BinaryColor::from(i % 8 < 4));
+
+        display.fill_contiguous(&area, colors).unwrap();
+
+        // Two runs of one byte in each of the 2 rows
+        assert_eq!(display.mode.buffer.writes, 4);
+        assert_eq!(&display.buffer()[0..7], &[0x18, 0x18, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fill_contiguous_matches_draw_iter() {
+        let area = Rectangle::new(Point::new(-3, 5), Size::new(40, 70));
+        // Runs of varying lengths, with some rows of a single color
+        let color = |i: u32| BinaryColor::from((i / 40) & 3 == 0 || (i * 7 / 11) & 2 == 0);
+
+        for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate270] {
+            let mut filled = display(rotation);
+            let mut drawn = display(rotation);
+
+            filled
+                .fill_contiguous(&area, (0..40 * 70).map(color))
+                .unwrap();
+            drawn
+                .draw_iter(
+                    area.points()
+                        .zip((0..40 * 70).map(color))
+                        .map(|(point, color)| Pixel(point, color)),
+                )
+                .unwrap();
+
+            assert_eq!(filled.buffer(), drawn.buffer());
+        }
+    }
+}
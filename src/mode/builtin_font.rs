@@ -0,0 +1,302 @@
+//! Built-in 5x7 pixel font for drawing text in the buffered graphics mode without
+//! embedded-graphics.
+
+#[cfg(feature = "async")]
+use crate::{mode::BufferedGraphicsModeAsync, size::DisplaySizeAsync, Sh1106Async};
+use crate::{
+    mode::{BufferedGraphicsMode, Shadow},
+    size::DisplaySize,
+    Sh1106,
+};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::WriteOnlyDataCommand;
+
+/// Width of a glyph in pixels
+const GLYPH_WIDTH: u32 = 5;
+
+/// Height of a glyph in pixels
+const GLYPH_HEIGHT: u32 = 7;
+
+/// Glyph drawn for characters the font doesn't have
+const BOX: [u8; 5] = [0x7f, 0x41, 0x41, 0x41, 0x7f];
+
+/// Glyphs for the printable ASCII characters from space to `~`, one byte per column with the
+/// least significant bit at the top.
+const GLYPHS: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // space
+    [0x00, 0x00, 0x5f, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7f, 0x14, 0x7f, 0x14], // #
+    [0x24, 0x2a, 0x7f, 0x2a, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x55, 0x22, 0x50], // &
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '
+    [0x00, 0x1c, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1c, 0x00], // )
+    [0x08, 0x2a, 0x1c, 0x2a, 0x08], // *
+    [0x08, 0x08, 0x3e, 0x08, 0x08], // +
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x60, 0x60, 0x00, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3e, 0x51, 0x49, 0x45, 0x3e], // 0
+    [0x00, 0x42, 0x7f, 0x40, 0x00], // 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x45, 0x4b, 0x31], // 3
+    [0x18, 0x14, 0x12, 0x7f, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3c, 0x4a, 0x49, 0x49, 0x30], // 6
+    [0x01, 0x71, 0x09, 0x05, 0x03], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x06, 0x49, 0x49, 0x29, 0x1e], // 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], // :
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ;
+    [0x08, 0x14, 0x22, 0x41, 0x00], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x00, 0x41, 0x22, 0x14, 0x08], // >
+    [0x02, 0x01, 0x51, 0x09, 0x06], // ?
+    [0x32, 0x49, 0x79, 0x41, 0x3e], // @
+    [0x7e, 0x11, 0x11, 0x11, 0x7e], // A
+    [0x7f, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3e, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7f, 0x41, 0x41, 0x22, 0x1c], // D
+    [0x7f, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7f, 0x09, 0x09, 0x09, 0x01], // F
+    [0x3e, 0x41, 0x49, 0x49, 0x7a], // G
+    [0x7f, 0x08, 0x08, 0x08, 0x7f], // H
+    [0x00, 0x41, 0x7f, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3f, 0x01], // J
+    [0x7f, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7f, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7f, 0x02, 0x0c, 0x02, 0x7f], // M
+    [0x7f, 0x04, 0x08, 0x10, 0x7f], // N
+    [0x3e, 0x41, 0x41, 0x41, 0x3e], // O
+    [0x7f, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3e, 0x41, 0x51, 0x21, 0x5e], // Q
+    [0x7f, 0x09, 0x19, 0x29, 0x46], // R
+    [0x46, 0x49, 0x49, 0x49, 0x31], // S
+    [0x01, 0x01, 0x7f, 0x01, 0x01], // T
+    [0x3f, 0x40, 0x40, 0x40, 0x3f], // U
+    [0x1f, 0x20, 0x40, 0x20, 0x1f], // V
+    [0x3f, 0x40, 0x38, 0x40, 0x3f], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x07, 0x08, 0x70, 0x08, 0x07], // Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], // Z
+    [0x00, 0x7f, 0x41, 0x41, 0x00], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // backslash
+    [0x00, 0x41, 0x41, 0x7f, 0x00], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x40, 0x40, 0x40, 0x40, 0x40], // _
+    [0x00, 0x01, 0x02, 0x04, 0x00], // `
+    [0x20, 0x54, 0x54, 0x54, 0x78], // a
+    [0x7f, 0x48, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x20], // c
+    [0x38, 0x44, 0x44, 0x48, 0x7f], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x08, 0x7e, 0x09, 0x01, 0x02], // f
+    [0x0c, 0x52, 0x52, 0x52, 0x3e], // g
+    [0x7f, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7d, 0x40, 0x00], // i
+    [0x20, 0x40, 0x44, 0x3d, 0x00], // j
+    [0x7f, 0x10, 0x28, 0x44, 0x00], // k
+    [0x00, 0x41, 0x7f, 0x40, 0x00], // l
+    [0x7c, 0x04, 0x18, 0x04, 0x78], // m
+    [0x7c, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0x7c, 0x14, 0x14, 0x14, 0x08], // p
+    [0x08, 0x14, 0x14, 0x18, 0x7c], // q
+    [0x7c, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x20], // s
+    [0x04, 0x3f, 0x44, 0x40, 0x20], // t
+    [0x3c, 0x40, 0x40, 0x20, 0x7c], // u
+    [0x1c, 0x20, 0x40, 0x20, 0x1c], // v
+    [0x3c, 0x40, 0x30, 0x40, 0x3c], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x0c, 0x50, 0x50, 0x50, 0x3c], // y
+    [0x44, 0x64, 0x54, 0x4c, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x7f, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x08, 0x04, 0x08, 0x10, 0x08], // ~
+];
+
+/// How [`Sh1106::draw_text_centered_with`] draws text wider than the display.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum TextOverflow {
+    /// Draw the whole text from the left edge, cutting it off at the right edge.
+    #[default]
+    LeftAlign,
+    /// Draw as much of the text as fits from the left edge, followed by `...`.
+    Ellipsis,
+}
+
+/// Get the width in pixels of `chars` characters, without the gap after the last one
+fn text_width(chars: usize) -> u32 {
+    (chars as u32)
+        .saturating_mul(GLYPH_WIDTH + 1)
+        .saturating_sub(1)
+}
+
+/// Get the glyph of a character, or a box for characters the font doesn't have.
+fn glyph(c: char) -> &'static [u8; 5] {
+    match c {
+        ' '..='~' => &GLYPHS[c as usize - ' ' as usize],
+        _ => &BOX,
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync")
+        )
+    )
+)]
+impl<DI, SIZE, SHADOW, BUF> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Draw `text` into the framebuffer with the built-in 5x7 pixel font, with the top left
+    /// corner of the first character at `x`, `y`. You need to call `disp.flush()` for any effect
+    /// on the screen.
+    ///
+    /// Characters are 6 pixels apart, leaving a 1 pixel gap, and pixels outside the display are
+    /// skipped. Only the lit pixels of each glyph are drawn, so the text is drawn on top of the
+    /// existing content. Characters other than printable ASCII, including control characters
+    /// such as newlines, are drawn as a box.
+    ///
+    /// This is available with the `builtin-font` feature, and doesn't need embedded-graphics, so
+    /// it is a small way of showing short strings such as numeric readouts on tiny
+    /// microcontrollers. Use the `embedded-graphics` fonts for anything more elaborate.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.draw_text(10, 8, "42");
+    ///
+    /// let page = &display.buffer()[128..256];
+    ///
+    /// // The columns of the "4", a gap, then the "2"
+    /// assert_eq!(&page[10..15], &[0x18, 0x14, 0x12, 0x7F, 0x10]);
+    /// assert_eq!(page[15], 0x00);
+    /// assert_eq!(&page[16..21], &[0x42, 0x61, 0x51, 0x49, 0x46]);
+    ///
+    /// // Nothing else is drawn
+    /// assert_eq!(page.iter().filter(|byte| **byte != 0).count(), 10);
+    /// assert!(display.buffer()[..128].iter().all(|byte| *byte == 0));
+    ///
+    /// // A character the font doesn't have is drawn as a box
+    /// display.draw_text(0, 0, "é");
+    /// assert_eq!(&display.buffer()[0..5], &[0x7F, 0x41, 0x41, 0x41, 0x7F]);
+    /// ```
+    pub fn draw_text(&mut self, x: u32, y: u32, text: &str) {
+        for (index, c) in text.chars().enumerate() {
+            let left = x.saturating_add((index as u32).saturating_mul(GLYPH_WIDTH + 1));
+
+            for (column, bits) in glyph(c).iter().enumerate() {
+                for row in 0..GLYPH_HEIGHT {
+                    if bits & (1 << row) != 0 {
+                        self.set_pixel(
+                            left.saturating_add(column as u32),
+                            y.saturating_add(row),
+                            true,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draw `text` with the built-in font horizontally centered on the display, with the top of
+    /// the characters at `y`, and return the x coordinate of its left edge. You need to call
+    /// `disp.flush()` for any effect on the screen.
+    ///
+    /// The width of the text doesn't include the gap after the last character. When the space
+    /// left over is odd, the extra pixel is on the right. Text wider than the display is drawn
+    /// from the left edge and cut off; use [`draw_text_centered_with`](Self::draw_text_centered_with)
+    /// to end it with `...` instead.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // "OK" is 5 + 1 + 5 = 11 pixels wide, leaving (128 - 11) / 2 = 58 pixels on the left
+    /// assert_eq!(display.draw_text_centered(0, "OK"), 58);
+    /// assert_eq!(&display.buffer()[58..63], &[0x3E, 0x41, 0x41, 0x41, 0x3E]);
+    /// assert_eq!(display.buffer()[57], 0x00);
+    ///
+    /// // 22 characters are 131 pixels wide, which doesn't fit
+    /// assert_eq!(display.draw_text_centered(8, "this is far too long!!"), 0);
+    /// ```
+    pub fn draw_text_centered(&mut self, y: u32, text: &str) -> u32 {
+        self.draw_text_centered_with(y, text, TextOverflow::LeftAlign)
+    }
+
+    /// Like [`draw_text_centered`](Self::draw_text_centered), with `overflow` choosing how
+    /// text wider than the display is drawn.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use sh1106::{mode::TextOverflow, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize64x48, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// // 11 characters are 65 pixels wide, so 7 are kept, followed by "..."
+    /// assert_eq!(
+    ///     display.draw_text_centered_with(0, "Temperature", TextOverflow::Ellipsis),
+    ///     0
+    /// );
+    ///
+    /// let mut expected = Sh1106::new(StubInterface, DisplaySize64x48, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    /// expected.draw_text(0, 0, "Tempera...");
+    ///
+    /// assert_eq!(display.buffer(), expected.buffer());
+    /// ```
+    pub fn draw_text_centered_with(&mut self, y: u32, text: &str, overflow: TextOverflow) -> u32 {
+        let display_width = u32::from(self.dimensions().0);
+        let chars = text.chars().count();
+        let width = text_width(chars);
+
+        if width <= display_width {
+            let x = (display_width - width) / 2;
+            self.draw_text(x, y, text);
+
+            return x;
+        }
+
+        match overflow {
+            TextOverflow::LeftAlign => self.draw_text(0, y, text),
+            TextOverflow::Ellipsis => {
+                // Characters which fit, including the 3 dots
+                let fit = ((display_width + 1) / (GLYPH_WIDTH + 1)) as usize;
+                let kept = fit.saturating_sub(3);
+                let end = text.char_indices().nth(kept).map_or(text.len(), |(i, _)| i);
+
+                self.draw_text(0, y, &text[..end]);
+                self.draw_text(kept as u32 * (GLYPH_WIDTH + 1), y, "...");
+            }
+        }
+
+        0
+    }
+}
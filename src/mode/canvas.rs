@@ -0,0 +1,224 @@
+//! Off-screen canvas larger than the display.
+
+#[cfg(feature = "async")]
+use crate::{mode::BufferedGraphicsModeAsync, size::DisplaySizeAsync, Sh1106Async};
+use crate::{
+    mode::{BufferedGraphicsMode, Shadow},
+    size::DisplaySize,
+    Sh1106,
+};
+use core::convert::Infallible;
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::WriteOnlyDataCommand;
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    pixelcolor::BinaryColor,
+    Pixel,
+};
+
+/// How [`Sh1106::blit_window`] handles a window which extends past the edge of a
+/// [`VirtualCanvas`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CanvasEdge {
+    /// Move the window back inside the canvas. Parts of the display not covered by a canvas
+    /// smaller than the display are cleared.
+    Clamp,
+    /// Continue on the opposite edge of the canvas, for example for a ticker which loops.
+    Wrap,
+}
+
+/// A monochrome canvas which is not tied to a display, and can be larger than it.
+///
+/// Content is drawn into the canvas with embedded-graphics, and a display sized window of it is
+/// copied into the framebuffer of a display with [`Sh1106::blit_window`]. The canvas is `W`
+/// pixels wide and `PAGES` pages, 8 pixels each, high. The height is given in pages so the size of
+/// the buffer can be computed on stable Rust.
+///
+/// ```rust
+/// # use sh1106::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use embedded_graphics::{
+///     pixelcolor::BinaryColor,
+///     prelude::*,
+///     primitives::{Line, PrimitiveStyle},
+/// };
+/// use sh1106::{
+///     mode::{CanvasEdge, VirtualCanvas},
+///     prelude::*,
+///     Sh1106,
+/// };
+///
+/// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// // A 256x64px ticker, with a vertical line at x = 200
+/// let mut canvas = VirtualCanvas::<256, 8>::new().with_edge(CanvasEdge::Wrap);
+/// Line::new(Point::new(200, 0), Point::new(200, 63))
+///     .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+///     .draw(&mut canvas)
+///     .unwrap();
+///
+/// display.blit_window(&canvas, Point::new(100, 0));
+/// assert_eq!(display.buffer()[100], 0xFF);
+///
+/// // Wrapping around the end of the canvas starts at its beginning again
+/// display.blit_window(&canvas, Point::new(200 + 256, 0));
+/// assert_eq!(display.buffer()[0], 0xFF);
+/// assert_eq!(display.buffer()[1], 0x00);
+///
+/// // Clamping keeps the window inside the canvas, so it starts at x = 256 - 128
+/// let canvas = canvas.with_edge(CanvasEdge::Clamp);
+/// display.blit_window(&canvas, Point::new(200, 0));
+/// assert_eq!(display.buffer()[200 - 128], 0xFF);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VirtualCanvas<const W: usize, const PAGES: usize> {
+    buffer: [[u8; W]; PAGES],
+    edge: CanvasEdge,
+}
+
+impl<const W: usize, const PAGES: usize> VirtualCanvas<W, PAGES> {
+    /// Create an empty canvas which clamps windows to its edges.
+    pub const fn new() -> Self {
+        Self {
+            buffer: [[0; W]; PAGES],
+            edge: CanvasEdge::Clamp,
+        }
+    }
+
+    /// Change how windows extending past the edge of the canvas are handled.
+    pub fn with_edge(self, edge: CanvasEdge) -> Self {
+        Self { edge, ..self }
+    }
+
+    /// Get how windows extending past the edge of the canvas are handled.
+    pub fn edge(&self) -> CanvasEdge {
+        self.edge
+    }
+
+    /// Get the width and height of the canvas in pixels.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (W, PAGES * 8)
+    }
+
+    /// Get whether the pixel at `x`, `y` is on. Pixels outside the canvas are off.
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        self.buffer
+            .get(y / 8)
+            .and_then(|page| page.get(x))
+            .is_some_and(|byte| byte & (1 << (y % 8)) != 0)
+    }
+
+    /// Turn the pixel at `x`, `y` on or off. Pixels outside the canvas are ignored.
+    pub fn set_pixel(&mut self, x: usize, y: usize, value: bool) {
+        if let Some(byte) = self.buffer.get_mut(y / 8).and_then(|page| page.get_mut(x)) {
+            let bit = 1 << (y % 8);
+
+            if value {
+                *byte |= bit;
+            } else {
+                *byte &= !bit;
+            }
+        }
+    }
+
+    /// Turn all pixels on or off.
+    pub fn fill(&mut self, value: bool) {
+        self.buffer = [[if value { 0xFF } else { 0x00 }; W]; PAGES];
+    }
+
+    /// Get the canvas pixel shown at `x`, `y` of a window of `window` pixels at `offset`.
+    fn window_pixel(&self, offset: Point, window: (u32, u32), x: u32, y: u32) -> bool {
+        let (width, height) = (W as i64, (PAGES * 8) as i64);
+
+        let position = |offset: i32, size: u32, canvas: i64, position: u32| match self.edge {
+            CanvasEdge::Clamp => {
+                let offset = i64::from(offset).min(canvas - i64::from(size)).max(0);
+
+                offset + i64::from(position)
+            }
+            CanvasEdge::Wrap => (i64::from(offset) + i64::from(position)).rem_euclid(canvas),
+        };
+
+        if width == 0 || height == 0 {
+            return false;
+        }
+
+        let x = position(offset.x, window.0, width, x);
+        let y = position(offset.y, window.1, height, y);
+
+        self.pixel(x as usize, y as usize)
+    }
+}
+
+impl<const W: usize, const PAGES: usize> Default for VirtualCanvas<W, PAGES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const PAGES: usize> OriginDimensions for VirtualCanvas<W, PAGES> {
+    fn size(&self) -> Size {
+        Size::new(W as u32, (PAGES * 8) as u32)
+    }
+}
+
+impl<const W: usize, const PAGES: usize> DrawTarget for VirtualCanvas<W, PAGES> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(pos, color) in pixels {
+            if pos.x >= 0 && pos.y >= 0 {
+                self.set_pixel(pos.x as usize, pos.y as usize, color.is_on());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync")
+        )
+    )
+)]
+impl<DI, SIZE, SHADOW, BUF> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Copy a display sized window of `canvas`, with its top left corner at `offset` in the
+    /// canvas, into the framebuffer.
+    ///
+    /// The window has the size of [`dimensions`](Sh1106::dimensions), so it follows the rotation
+    /// of the display. How a window extending past the edge of the canvas is handled depends on
+    /// the [`CanvasEdge`] of the canvas. Call [`flush`](Sh1106::flush) to show the window.
+    pub fn blit_window<const W: usize, const PAGES: usize>(
+        &mut self,
+        canvas: &VirtualCanvas<W, PAGES>,
+        offset: Point,
+    ) {
+        let (width, height) = self.dimensions();
+        let window = (u32::from(width), u32::from(height));
+
+        for y in 0..window.1 {
+            for x in 0..window.0 {
+                self.set_pixel(x, y, canvas.window_pixel(offset, window, x, y));
+            }
+        }
+    }
+}
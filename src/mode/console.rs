@@ -0,0 +1,298 @@
+use super::font::{char_to_bitmap, rotate_bitmap};
+#[cfg(feature = "async")]
+use crate::mode::DisplayConfigAsync;
+use crate::Error;
+use crate::{mode::DisplayConfig, rotation::DisplayRotation, size::DisplaySize, Sh1106};
+#[cfg(feature = "async")]
+use crate::{size::DisplaySizeAsync, Sh1106Async};
+use core::fmt;
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::WriteOnlyDataCommand;
+
+/// Tab stops are placed every this many characters
+const TAB_WIDTH: u8 = 4;
+
+/// Console mode.
+///
+/// A bufferless, append-only log using the same 8x8px font as
+/// [`TerminalMode`](crate::mode::TerminalMode). Text is always added on the bottom line, and
+/// each newline scrolls the older lines up using the display start line. Characters are drawn
+/// straight to the display, so there is nothing to flush. The cursor can't be moved other than
+/// with `\r`, which returns to the start of the line, and `\t`, which moves to the next tab stop
+/// every 4 characters.
+///
+/// Scrolling is not possible when the display is rotated by 90 or 270 degrees. In that case text
+/// starts on the top line, and after the bottom line the log wraps back to the top, clearing one
+/// line at a time.
+///
+/// ```rust
+/// # use sh1106::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use core::fmt::Write;
+/// use sh1106::{prelude::*, Sh1106};
+///
+/// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_console_mode();
+/// display.init().unwrap();
+///
+/// writeln!(display, "boot: {}", "ok").unwrap();
+/// ```
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(feature = "async", idents(ConsoleMode(async = "ConsoleModeAsync")))
+)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ConsoleMode {
+    /// Character column of the cursor
+    column: u8,
+    /// Character line of the cursor, only used when rotated by 90 or 270 degrees
+    line: u8,
+    /// The display RAM page shown as the top line of the display
+    top_page: u8,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(feature = "async", idents(ConsoleMode(async = "ConsoleModeAsync")))
+)]
+impl ConsoleMode {
+    /// Create a new console mode config instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            DisplayConfig(async = "DisplayConfigAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            ConsoleMode(async = "ConsoleModeAsync"),
+        )
+    )
+)]
+impl<DI, SIZE> DisplayConfig for Sh1106<DI, SIZE, ConsoleMode>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    type Error = Error;
+
+    /// Set the display rotation
+    ///
+    /// This method clears the display, as the existing lines can't be scrolled in the new
+    /// orientation.
+    async fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), Error> {
+        self.set_rotation(rot).await?;
+        self.clear().await
+    }
+
+    /// Initialise the display and clear the log.
+    async fn init(&mut self) -> Result<(), Error> {
+        self.init_default().await?;
+        self.clear().await
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            ConsoleMode(async = "ConsoleModeAsync"),
+        )
+    )
+)]
+impl<DI, SIZE> Sh1106<DI, SIZE, ConsoleMode>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// Clear the display and move the cursor to the start of the first line.
+    pub async fn clear(&mut self) -> Result<(), Error> {
+        for page in 0..SIZE::HEIGHT / 8 {
+            self.clear_page(SIZE::OFFSETY / 8 + page).await?;
+        }
+
+        self.mode = ConsoleMode::new();
+        self.set_start_line(0).await?;
+
+        self.move_to_cursor().await
+    }
+
+    /// Print a character to the log.
+    ///
+    /// A newline scrolls the display up by one line and clears the new bottom line.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_console_mode();
+    ///
+    /// display.print_char('\n').unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // Show RAM row 8 at the top, clear page 0, then move to its first column
+    /// assert_eq!(
+    ///     interface.commands(),
+    ///     &[0x48, 0xB0, 0x02, 0x10, 0xB0, 0x02, 0x10]
+    /// );
+    /// assert_eq!(interface.data(), &[0; 128]);
+    /// ```
+    pub async fn print_char(&mut self, c: char) -> Result<(), Error> {
+        match c {
+            '\n' => self.new_line().await,
+            '\r' => {
+                self.mode.column = 0;
+                self.move_to_cursor().await
+            }
+            '\t' => {
+                let spaces = TAB_WIDTH - self.mode.column % TAB_WIDTH;
+
+                for _ in 0..spaces {
+                    self.print_glyph(' ').await?;
+                }
+
+                Ok(())
+            }
+            _ => self.print_glyph(c).await,
+        }
+    }
+
+    /// Draw a character at the cursor, starting a new line first if the current one is full.
+    async fn print_glyph(&mut self, c: char) -> Result<(), Error> {
+        let (columns, _) = self.console_size();
+
+        if self.mode.column >= columns {
+            self.new_line().await?;
+        }
+
+        let bitmap = match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => char_to_bitmap(c),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                // Each character is in a different page, so the address has to be set every time
+                self.move_to_cursor().await?;
+
+                rotate_bitmap(char_to_bitmap(c))
+            }
+        };
+
+        self.draw(&bitmap).await?;
+        self.mode.column += 1;
+
+        Ok(())
+    }
+
+    /// Start a new, empty line.
+    async fn new_line(&mut self) -> Result<(), Error> {
+        let (_, lines) = self.console_size();
+
+        self.mode.column = 0;
+
+        match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                // Show the next display RAM page at the bottom of the screen, and clear it for
+                // the new line
+                self.mode.top_page = (self.mode.top_page + 1) % (SIZE::DRIVER_ROWS / 8);
+                self.set_start_line(self.mode.top_page * 8).await?;
+                self.clear_page(self.bottom_page()).await?;
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                self.mode.line = (self.mode.line + 1) % lines;
+                self.clear_rotated_line(self.mode.line).await?;
+            }
+        }
+
+        self.move_to_cursor().await
+    }
+
+    /// Point the display RAM address at the cursor
+    async fn move_to_cursor(&mut self) -> Result<(), Error> {
+        let column = self.mode.column;
+
+        match self.rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                self.set_row(self.bottom_page() * 8).await?;
+                self.set_column(column * 8).await
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                self.set_row(SIZE::OFFSETY + column * 8).await?;
+                self.set_column(self.mode.line * 8).await
+            }
+        }
+    }
+
+    /// The display RAM page shown as the bottom line of the display
+    fn bottom_page(&self) -> u8 {
+        let (_, lines) = self.console_size();
+
+        (self.mode.top_page + SIZE::OFFSETY / 8 + lines - 1) % (SIZE::DRIVER_ROWS / 8)
+    }
+
+    /// Number of characters per line and number of lines
+    fn console_size(&self) -> (u8, u8) {
+        let (width, height) = self.dimensions();
+
+        (width / 8, height / 8)
+    }
+
+    /// Clear a single page of the display RAM
+    async fn clear_page(&mut self, page: u8) -> Result<(), Error> {
+        self.set_row(page * 8).await?;
+        self.set_column(0).await?;
+
+        for _ in 0..SIZE::WIDTH / 8 {
+            self.draw(&[0; 8]).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Clear a line of characters when rotated by 90 or 270 degrees, which is a strip of 8
+    /// display RAM columns
+    async fn clear_rotated_line(&mut self, line: u8) -> Result<(), Error> {
+        for page in 0..SIZE::HEIGHT / 8 {
+            self.set_row(SIZE::OFFSETY + page * 8).await?;
+            self.set_column(line * 8).await?;
+            self.draw(&[0; 8]).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<DI, SIZE> Sh1106Async<DI, SIZE, ConsoleModeAsync>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySizeAsync,
+{
+    /// Write a string slice to the log
+    pub async fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        for c in s.chars() {
+            self.print_char(c).await?;
+        }
+        Ok(())
+    }
+}
+
+impl<DI, SIZE> fmt::Write for Sh1106<DI, SIZE, ConsoleMode>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        s.chars()
+            .try_for_each(|c| self.print_char(c).map_err(|_| fmt::Error))
+    }
+}
@@ -0,0 +1,112 @@
+//! Ordered dithering of grayscale drawing onto a monochrome draw target.
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::Dimensions,
+    pixelcolor::{BinaryColor, Gray8, GrayColor},
+    primitives::Rectangle,
+    Pixel,
+};
+
+/// The 4x4 Bayer matrix, giving the order pixels are turned on in as the gray level increases.
+pub const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// A draw target which accepts [`Gray8`] pixels and draws them to a [`BinaryColor`] target,
+/// such as a display in [`BufferedGraphicsMode`](crate::mode::BufferedGraphicsMode), using
+/// ordered dithering.
+///
+/// Each pixel is compared against the entry of a 4x4 matrix chosen by its position, so an area
+/// of constant gray turns on the same share of its pixels in a regular pattern. The matrix holds
+/// the ranks 0 to 15, each standing for a gray level of `rank * 16 + 8`. A pixel is turned on if
+/// its luma is above the level of its rank, shifted by the threshold. The default threshold of
+/// 128 doesn't shift the levels. Higher thresholds make the image darker, lower ones lighter.
+///
+/// ```rust
+/// # use sh1106::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use embedded_graphics::{pixelcolor::Gray8, prelude::*};
+/// use sh1106::{mode::DitheredTarget, prelude::*, Sh1106};
+///
+/// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// let mut dithered = DitheredTarget::new(&mut display);
+/// let area = dithered.bounding_box();
+/// dithered.fill_solid(&area, Gray8::new(128)).unwrap();
+///
+/// // Mid gray turns on every other pixel in a checkerboard
+/// assert!(display
+///     .buffer()
+///     .chunks(2)
+///     .all(|columns| columns == [0b0101_0101, 0b1010_1010]));
+/// ```
+#[derive(Debug)]
+pub struct DitheredTarget<'a, T> {
+    target: &'a mut T,
+    matrix: [[u8; 4]; 4],
+    threshold: u8,
+}
+
+impl<'a, T> DitheredTarget<'a, T>
+where
+    T: DrawTarget<Color = BinaryColor>,
+{
+    /// Dither pixels drawn to `target` with the [`BAYER_4X4`] matrix and a threshold of 128.
+    pub fn new(target: &'a mut T) -> Self {
+        Self {
+            target,
+            matrix: BAYER_4X4,
+            threshold: 128,
+        }
+    }
+
+    /// Use a different 4x4 matrix of ranks from 0 to 15. Ranks above 15 never turn a pixel on.
+    pub fn with_matrix(self, matrix: [[u8; 4]; 4]) -> Self {
+        Self { matrix, ..self }
+    }
+
+    /// Change the threshold. The default is 128, higher values make the image darker.
+    pub fn with_threshold(self, threshold: u8) -> Self {
+        Self { threshold, ..self }
+    }
+}
+
+/// Get whether a pixel of the given luma at `x`, `y` is turned on.
+fn is_on(matrix: &[[u8; 4]; 4], threshold: u8, x: i32, y: i32, luma: u8) -> bool {
+    let rank = matrix[y.rem_euclid(4) as usize][x.rem_euclid(4) as usize];
+    let level = i32::from(rank) * 16 + 8;
+
+    i32::from(luma) + 128 - i32::from(threshold) > level
+}
+
+impl<T> Dimensions for DitheredTarget<'_, T>
+where
+    T: DrawTarget<Color = BinaryColor>,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<T> DrawTarget for DitheredTarget<'_, T>
+where
+    T: DrawTarget<Color = BinaryColor>,
+{
+    type Color = Gray8;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (matrix, threshold) = (self.matrix, self.threshold);
+
+        self.target
+            .draw_iter(pixels.into_iter().map(|Pixel(pos, color)| {
+                Pixel(
+                    pos,
+                    is_on(&matrix, threshold, pos.x, pos.y, color.luma()).into(),
+                )
+            }))
+    }
+}
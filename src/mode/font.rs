@@ -0,0 +1,229 @@
+//! 8x8 pixel font shared by the text modes.
+
+/// Get the 8x8 bitmap of a character, one byte per column with the least significant bit at the
+/// top.
+pub(super) fn char_to_bitmap(input: char) -> [u8; 8] {
+    const CHARS: [[u8; 6]; 95] = [
+        // !
+        [0x00, 0x2f, 0x00, 0x00, 0x00, 0x00],
+        // "
+        [0x03, 0x00, 0x03, 0x00, 0x00, 0x00],
+        // #
+        [0x12, 0x3f, 0x12, 0x12, 0x3f, 0x12],
+        // $
+        [0x2e, 0x2a, 0x7f, 0x2a, 0x3a, 0x00],
+        // %
+        [0x23, 0x13, 0x08, 0x04, 0x32, 0x31],
+        // &
+        [0x10, 0x2a, 0x25, 0x2a, 0x10, 0x20],
+        // '
+        [0x02, 0x01, 0x00, 0x00, 0x00, 0x00],
+        // (
+        [0x1e, 0x21, 0x00, 0x00, 0x00, 0x00],
+        // )
+        [0x21, 0x1e, 0x00, 0x00, 0x00, 0x00],
+        // *
+        [0x08, 0x2a, 0x1c, 0x2a, 0x08, 0x00],
+        // +
+        [0x08, 0x08, 0x3e, 0x08, 0x08, 0x00],
+        // ,
+        [0x80, 0x60, 0x00, 0x00, 0x00, 0x00],
+        // -
+        [0x08, 0x08, 0x08, 0x08, 0x08, 0x00],
+        // .
+        [0x30, 0x30, 0x00, 0x00, 0x00, 0x00],
+        // /
+        [0x20, 0x10, 0x08, 0x04, 0x02, 0x00],
+        // 0
+        [0x1e, 0x31, 0x29, 0x25, 0x23, 0x1e],
+        // 1
+        [0x22, 0x21, 0x3f, 0x20, 0x20, 0x20],
+        // 2
+        [0x32, 0x29, 0x29, 0x29, 0x29, 0x26],
+        // 3
+        [0x12, 0x21, 0x21, 0x25, 0x25, 0x1a],
+        // 4
+        [0x18, 0x14, 0x12, 0x3f, 0x10, 0x00],
+        // 5
+        [0x17, 0x25, 0x25, 0x25, 0x25, 0x19],
+        // 6
+        [0x1e, 0x25, 0x25, 0x25, 0x25, 0x18],
+        // 7
+        [0x01, 0x01, 0x31, 0x09, 0x05, 0x03],
+        // 8
+        [0x1a, 0x25, 0x25, 0x25, 0x25, 0x1a],
+        // 9
+        [0x06, 0x29, 0x29, 0x29, 0x29, 0x1e],
+        // :
+        [0x24, 0x00, 0x00, 0x00, 0x00, 0x00],
+        // ;
+        [0x80, 0x64, 0x00, 0x00, 0x00, 0x00],
+        // <
+        [0x08, 0x14, 0x22, 0x00, 0x00, 0x00],
+        // =
+        [0x14, 0x14, 0x14, 0x14, 0x14, 0x00],
+        // >
+        [0x22, 0x14, 0x08, 0x00, 0x00, 0x00],
+        // ?
+        [0x02, 0x01, 0x01, 0x29, 0x05, 0x02],
+        // @
+        [0x1e, 0x21, 0x2d, 0x2b, 0x2d, 0x0e],
+        // A
+        [0x3e, 0x09, 0x09, 0x09, 0x09, 0x3e],
+        // B
+        [0x3f, 0x25, 0x25, 0x25, 0x25, 0x1a],
+        // C
+        [0x1e, 0x21, 0x21, 0x21, 0x21, 0x12],
+        // D
+        [0x3f, 0x21, 0x21, 0x21, 0x12, 0x0c],
+        // E
+        [0x3f, 0x25, 0x25, 0x25, 0x25, 0x21],
+        // F
+        [0x3f, 0x05, 0x05, 0x05, 0x05, 0x01],
+        // G
+        [0x1e, 0x21, 0x21, 0x21, 0x29, 0x1a],
+        // H
+        [0x3f, 0x04, 0x04, 0x04, 0x04, 0x3f],
+        // I
+        [0x21, 0x21, 0x3f, 0x21, 0x21, 0x00],
+        // J
+        [0x10, 0x20, 0x20, 0x20, 0x20, 0x1f],
+        // K
+        [0x3f, 0x04, 0x0c, 0x0a, 0x11, 0x20],
+        // L
+        [0x3f, 0x20, 0x20, 0x20, 0x20, 0x20],
+        // M
+        [0x3f, 0x02, 0x04, 0x04, 0x02, 0x3f],
+        // N
+        [0x3f, 0x02, 0x04, 0x08, 0x10, 0x3f],
+        // O
+        [0x1e, 0x21, 0x21, 0x21, 0x21, 0x1e],
+        // P
+        [0x3f, 0x09, 0x09, 0x09, 0x09, 0x06],
+        // Q
+        [0x1e, 0x21, 0x29, 0x31, 0x21, 0x5e],
+        // R
+        [0x3f, 0x09, 0x09, 0x09, 0x19, 0x26],
+        // S
+        [0x12, 0x25, 0x25, 0x25, 0x25, 0x18],
+        // T
+        [0x01, 0x01, 0x01, 0x3f, 0x01, 0x01],
+        // U
+        [0x1f, 0x20, 0x20, 0x20, 0x20, 0x1f],
+        // V
+        [0x0f, 0x10, 0x20, 0x20, 0x10, 0x0f],
+        // W
+        [0x1f, 0x20, 0x10, 0x10, 0x20, 0x1f],
+        // X
+        [0x21, 0x12, 0x0c, 0x0c, 0x12, 0x21],
+        // Y
+        [0x01, 0x02, 0x3c, 0x02, 0x01, 0x00],
+        // Z
+        [0x21, 0x31, 0x29, 0x25, 0x23, 0x21],
+        // [
+        [0x3f, 0x21, 0x00, 0x00, 0x00, 0x00],
+        // \
+        [0x02, 0x04, 0x08, 0x10, 0x20, 0x00],
+        // ]
+        [0x21, 0x3f, 0x00, 0x00, 0x00, 0x00],
+        // ^
+        [0x04, 0x02, 0x3f, 0x02, 0x04, 0x00],
+        // _
+        [0x40, 0x40, 0x40, 0x40, 0x40, 0x40],
+        // `
+        [0x01, 0x02, 0x00, 0x00, 0x00, 0x00],
+        // a
+        [0x10, 0x2a, 0x2a, 0x2a, 0x3c, 0x00],
+        // b
+        [0x3f, 0x24, 0x24, 0x24, 0x18, 0x00],
+        // c
+        [0x1c, 0x22, 0x22, 0x22, 0x00, 0x00],
+        // d
+        [0x18, 0x24, 0x24, 0x24, 0x3f, 0x00],
+        // e
+        [0x1c, 0x2a, 0x2a, 0x2a, 0x24, 0x00],
+        // f
+        [0x00, 0x3e, 0x05, 0x01, 0x00, 0x00],
+        // g
+        [0x18, 0xa4, 0xa4, 0xa4, 0x7c, 0x00],
+        // h
+        [0x3f, 0x04, 0x04, 0x04, 0x38, 0x00],
+        // i
+        [0x00, 0x24, 0x3d, 0x20, 0x00, 0x00],
+        // j
+        [0x20, 0x40, 0x40, 0x3d, 0x00, 0x00],
+        // k
+        [0x3f, 0x0c, 0x12, 0x20, 0x00, 0x00],
+        // l
+        [0x1f, 0x20, 0x20, 0x00, 0x00, 0x00],
+        // m
+        [0x3e, 0x02, 0x3c, 0x02, 0x3c, 0x00],
+        // n
+        [0x3e, 0x02, 0x02, 0x02, 0x3c, 0x00],
+        // o
+        [0x1c, 0x22, 0x22, 0x22, 0x1c, 0x00],
+        // p
+        [0xfc, 0x24, 0x24, 0x24, 0x18, 0x00],
+        // q
+        [0x18, 0x24, 0x24, 0x24, 0xfc, 0x00],
+        // r
+        [0x3e, 0x04, 0x02, 0x02, 0x00, 0x00],
+        // s
+        [0x24, 0x2a, 0x2a, 0x2a, 0x10, 0x00],
+        // t
+        [0x02, 0x1f, 0x22, 0x20, 0x00, 0x00],
+        // u
+        [0x1e, 0x20, 0x20, 0x20, 0x1e, 0x00],
+        // v
+        [0x06, 0x18, 0x20, 0x18, 0x06, 0x00],
+        // w
+        [0x1e, 0x30, 0x1c, 0x30, 0x1e, 0x00],
+        // x
+        [0x22, 0x14, 0x08, 0x14, 0x22, 0x00],
+        // y
+        [0x1c, 0xa0, 0xa0, 0xa0, 0x7c, 0x00],
+        // z
+        [0x22, 0x32, 0x2a, 0x26, 0x22, 0x00],
+        // {
+        [0x0c, 0x3f, 0x21, 0x00, 0x00, 0x00],
+        // |
+        [0x3f, 0x00, 0x00, 0x00, 0x00, 0x00],
+        // }
+        [0x21, 0x3f, 0x0c, 0x00, 0x00, 0x00],
+        // ~
+        [0x02, 0x01, 0x02, 0x01, 0x00, 0x00],
+        // blank
+        [0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    ];
+
+    // Drawn in place of characters which are not in the font
+    const REPLACEMENT: [u8; 6] = [0x3f, 0x21, 0x21, 0x21, 0x21, 0x3f];
+
+    let g = if input.is_ascii() {
+        (input as usize)
+            .checked_sub(b'!'.into())
+            .and_then(|idx| CHARS.get(idx))
+            .unwrap_or(&CHARS[CHARS.len() - 1])
+    } else {
+        &REPLACEMENT
+    };
+
+    [0, g[0], g[1], g[2], g[3], g[4], g[5], 0]
+}
+
+/// Rotate a character bitmap by 90 degrees, for displays rotated by 90 or 270 degrees.
+pub(super) fn rotate_bitmap(bitmap: [u8; 8]) -> [u8; 8] {
+    let mut rotated: [u8; 8] = [0; 8];
+
+    for (col, source) in bitmap.iter().enumerate() {
+        // source.msb is the top pixel
+        for (row, item) in rotated.iter_mut().enumerate() {
+            let bit = source & 1 << row != 0;
+            if bit {
+                *item |= 1 << col;
+            }
+        }
+    }
+
+    rotated
+}
@@ -1,12 +1,40 @@
 //! Display modes.
 
 mod buffered_graphics;
+#[cfg(feature = "builtin-font")]
+mod builtin_font;
+#[cfg(feature = "graphics")]
+mod canvas;
+mod console;
+#[cfg(feature = "graphics")]
+mod dithered;
+mod font;
+mod raw;
+mod raw_guard;
+#[cfg(feature = "graphics")]
+mod seven_segment;
 mod terminal;
+#[cfg(feature = "graphics")]
+mod viewport;
 
+use crate::Error;
 use crate::{rotation::DisplayRotation, size::DisplaySize, Sh1106};
 pub use buffered_graphics::*;
-use display_interface::{DisplayError, WriteOnlyDataCommand};
+#[cfg(feature = "builtin-font")]
+pub use builtin_font::*;
+#[cfg(feature = "graphics")]
+pub use canvas::*;
+pub use console::*;
+use display_interface::WriteOnlyDataCommand;
+#[cfg(feature = "graphics")]
+pub use dithered::*;
+pub use raw::*;
+pub use raw_guard::*;
+#[cfg(feature = "graphics")]
+pub use seven_segment::*;
 pub use terminal::*;
+#[cfg(feature = "graphics")]
+pub use viewport::*;
 
 /// Common functions to all display modes.
 #[maybe_async_cfg::maybe(sync(keep_self,), async(feature = "async"))]
@@ -31,7 +59,7 @@ where
     SIZE: DisplaySize,
 {
     /// Clear the display.
-    pub fn clear(&mut self) -> Result<(), DisplayError> {
+    pub fn clear(&mut self) -> Result<(), Error> {
         let dim = self.dimensions();
         //self.set_draw_area((0, 0), dim)?;
 
@@ -57,15 +85,15 @@ where
     DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
 {
-    type Error = DisplayError;
+    type Error = Error;
 
     /// Set the display rotation.
-    fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DisplayError> {
+    fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), Error> {
         self.set_rotation(rot)
     }
 
     /// Initialise in horizontal addressing mode.
-    fn init(&mut self) -> Result<(), DisplayError> {
+    fn init(&mut self) -> Result<(), Error> {
         self.init_default()
     }
 }
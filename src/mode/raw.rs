@@ -0,0 +1,291 @@
+#[cfg(feature = "async")]
+use crate::mode::DisplayConfigAsync;
+use crate::Error;
+#[cfg(feature = "async")]
+use crate::{command::CommandAsync, size::DisplaySizeAsync, Sh1106Async};
+use crate::{
+    command::{Command, Page},
+    mode::DisplayConfig,
+    rotation::DisplayRotation,
+    size::DisplaySize,
+    Sh1106,
+};
+use core::{cmp::min, ops::Range};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::WriteOnlyDataCommand;
+
+/// Raw mode.
+///
+/// A bufferless mode for drivers which want to address the display RAM themselves. Data is
+/// written one page (8px high row) at a time, with the column address incrementing after every
+/// byte. Column addresses are relative to the visible area of the display, so the
+/// [`DisplaySize`] column offset is applied automatically.
+///
+/// [`set_draw_area`](Sh1106::set_draw_area) and [`draw_area`](Sh1106::draw_area) emulate a draw
+/// window on top of page addressing, moving to the start of the next page whenever the right edge
+/// of the area is reached.
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(feature = "async", idents(RawMode(async = "RawModeAsync")))
+)]
+#[derive(Debug, Copy, Clone)]
+pub struct RawMode {
+    start: (u8, u8),
+    end: (u8, u8),
+    /// The column and page the next byte of [`draw_area`](Sh1106::draw_area) is written to
+    cursor: (u8, u8),
+    /// The page last sent to the display, if any
+    page: Option<Page>,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(feature = "async", idents(RawMode(async = "RawModeAsync")))
+)]
+impl RawMode {
+    /// Create a new raw mode config instance, with a draw area covering a display of the given
+    /// size.
+    pub fn new(width: u8, height: u8) -> Self {
+        Self {
+            start: (0, 0),
+            end: (width, height),
+            cursor: (0, 0),
+            page: None,
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            DisplayConfig(async = "DisplayConfigAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            RawMode(async = "RawModeAsync"),
+        )
+    )
+)]
+impl<DI, SIZE> DisplayConfig for Sh1106<DI, SIZE, RawMode>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    type Error = Error;
+
+    /// Set the display rotation.
+    async fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), Error> {
+        self.set_rotation(rot).await
+    }
+
+    /// Initialise the display in page addressing mode.
+    async fn init(&mut self) -> Result<(), Error> {
+        self.init_default().await
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            Command(async = "CommandAsync"),
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            RawMode(async = "RawModeAsync"),
+        )
+    )
+)]
+impl<DI, SIZE> Sh1106<DI, SIZE, RawMode>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// Set the page (8px high row) of the display RAM where any sent data should be drawn.
+    ///
+    /// This is the same as [`set_current_page`](Sh1106::set_current_page).
+    pub async fn set_page(&mut self, page: Page) -> Result<(), Error> {
+        self.set_current_page(page).await
+    }
+
+    /// Set the page (8px high row) of the display RAM the next data is written to, and remember
+    /// it as the [`current_page`](Sh1106::current_page).
+    ///
+    /// Pages are counted from the top of the visible area. The display offset of panels showing
+    /// a window of the display RAM is applied by the display itself, so no offset is needed here.
+    /// The page doesn't change when data is written; only the column advances.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{command::Page, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_raw_mode();
+    ///
+    /// assert_eq!(display.current_page(), None);
+    ///
+    /// display.set_current_page(Page::Page3).unwrap();
+    /// assert_eq!(display.current_page(), Some(Page::Page3));
+    ///
+    /// // The draw area moves the page as well
+    /// display.set_draw_area((0, 40), (128, 48)).unwrap();
+    /// assert_eq!(display.current_page(), Some(Page::Page5));
+    ///
+    /// assert_eq!(display.release().commands(), &[0xB3, 0xB5, 0x02, 0x10]);
+    /// ```
+    pub async fn set_current_page(&mut self, page: Page) -> Result<(), Error> {
+        Command::PageStart(page).send(&mut self.interface).await?;
+        self.mode.page = Some(page);
+
+        Ok(())
+    }
+
+    /// Get the page last set by [`set_current_page`](Sh1106::set_current_page),
+    /// [`set_page`](Sh1106::set_page) or the draw area methods, or `None` if no page has been set
+    /// in this mode yet.
+    pub fn current_page(&self) -> Option<Page> {
+        self.mode.page
+    }
+
+    /// Set the area of the display written to by [`draw_area`](Sh1106::draw_area), and move to
+    /// its top left corner.
+    ///
+    /// `start` is inclusive and `end` is exclusive, both as (column, row) in pixels. Rows are
+    /// rounded out to whole pages, and the area is clipped to the size of the display.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_raw_mode();
+    ///
+    /// display.set_draw_area((4, 8), (6, 24)).unwrap();
+    /// display.draw_area(&[0x01, 0x02, 0x03, 0x04]).unwrap();
+    ///
+    /// let interface = display.release();
+    ///
+    /// // Column 4 (plus the column offset of 2) of page 1, then the same column of page 2
+    /// assert_eq!(
+    ///     interface.commands(),
+    ///     &[0xB1, 0x06, 0x10, 0xB2, 0x06, 0x10]
+    /// );
+    /// assert_eq!(interface.data(), &[0x01, 0x02, 0x03, 0x04]);
+    /// ```
+    pub async fn set_draw_area(&mut self, start: (u8, u8), end: (u8, u8)) -> Result<(), Error> {
+        let end = (min(end.0, SIZE::WIDTH), min(end.1, SIZE::HEIGHT));
+        let start = (min(start.0, end.0), min(start.1, end.1));
+
+        self.mode.start = (start.0, start.1 / 8);
+        self.mode.end = (end.0, end.1.div_ceil(8));
+        self.mode.cursor = self.mode.start;
+
+        if self.mode.cursor.1 < self.mode.end.1 {
+            self.move_to_cursor().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Stream data into the draw area set by [`set_draw_area`](Sh1106::set_draw_area).
+    ///
+    /// Each byte is a column of 8 pixels of the current page. When the right edge of the area is
+    /// reached, drawing continues at the left edge of the next page. Any data which does not fit
+    /// in the remaining area is dropped.
+    pub async fn draw_area(&mut self, mut data: &[u8]) -> Result<(), Error> {
+        while !data.is_empty() && self.mode.cursor.1 < self.mode.end.1 {
+            let remaining = self.mode.end.0.saturating_sub(self.mode.cursor.0);
+            let (chunk, rest) = data.split_at(min(remaining as usize, data.len()));
+
+            if !chunk.is_empty() {
+                self.draw(chunk).await?;
+            }
+
+            self.mode.cursor.0 += chunk.len() as u8;
+            data = rest;
+
+            if self.mode.cursor.0 >= self.mode.end.0 {
+                self.mode.cursor = (self.mode.start.0, self.mode.cursor.1 + 1);
+
+                if !data.is_empty() && self.mode.cursor.1 < self.mode.end.1 {
+                    self.move_to_cursor().await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fill the columns `cols` of the pages `pages` with a repeated `pattern` byte, without
+    /// needing a framebuffer, for example to clear a status bar.
+    ///
+    /// This sets the draw area to the window, as [`set_draw_area`](Sh1106::set_draw_area) does.
+    /// Columns are relative to the visible area of the display, and both ranges are clipped to
+    /// the size of the display. A range which starts after its end returns
+    /// [`Error::InvalidConfig`].
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Error, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_raw_mode();
+    ///
+    /// display.fill_window(6..10, 124..200, 0xFF).unwrap();
+    /// assert_eq!(
+    ///     display.fill_window(2..1, 0..128, 0x00),
+    ///     Err(Error::InvalidConfig("range starts after its end"))
+    /// );
+    ///
+    /// let interface = display.release();
+    ///
+    /// // Columns 124 to 127 (plus the column offset of 2) of pages 6 and 7
+    /// assert_eq!(
+    ///     interface.commands(),
+    ///     &[0xB6, 0x0E, 0x17, 0xB7, 0x0E, 0x17]
+    /// );
+    /// assert_eq!(interface.data(), &[0xFF; 8]);
+    /// ```
+    pub async fn fill_window(
+        &mut self,
+        pages: Range<u8>,
+        cols: Range<u8>,
+        pattern: u8,
+    ) -> Result<(), Error> {
+        if pages.start > pages.end || cols.start > cols.end {
+            return Err(Error::InvalidConfig("range starts after its end"));
+        }
+
+        let last_page = SIZE::HEIGHT.div_ceil(8);
+        let pages = min(pages.start, last_page)..min(pages.end, last_page);
+        let cols = min(cols.start, SIZE::WIDTH)..min(cols.end, SIZE::WIDTH);
+
+        self.set_draw_area((cols.start, pages.start * 8), (cols.end, pages.end * 8))
+            .await?;
+
+        let chunk = [pattern; 16];
+        let mut remaining = cols.len() * pages.len();
+
+        while remaining > 0 {
+            let len = min(remaining, chunk.len());
+
+            self.draw_area(&chunk[..len]).await?;
+            remaining -= len;
+        }
+
+        Ok(())
+    }
+
+    /// Point the display RAM address at the draw area cursor
+    async fn move_to_cursor(&mut self) -> Result<(), Error> {
+        let (column, page) = self.mode.cursor;
+
+        self.set_page(Page::from_row(page * 8)?).await?;
+        self.set_column(column).await
+    }
+}
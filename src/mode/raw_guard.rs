@@ -0,0 +1,165 @@
+//! Temporary raw access to the display RAM from the buffered graphics mode
+
+use crate::Error;
+#[cfg(feature = "async")]
+use crate::{
+    command::CommandAsync, mode::BufferedGraphicsModeAsync, size::DisplaySizeAsync, Sh1106Async,
+};
+use crate::{
+    command::{Command, Page},
+    mode::{BufferedGraphicsMode, Shadow},
+    size::DisplaySize,
+    Sh1106,
+};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::WriteOnlyDataCommand;
+
+/// Raw page and column writes to the display RAM, borrowed from a display in
+/// [`BufferedGraphicsMode`], returned by [`Sh1106::as_raw`].
+///
+/// Writes go straight to the display, like in [`RawMode`](crate::mode::RawMode), and the
+/// framebuffer is left untouched. When the guard is dropped, the whole framebuffer is marked as
+/// changed, so the next [`flush`](Sh1106::flush) overwrites whatever was written with the
+/// framebuffer and the display and framebuffer agree again.
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync"),
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            Sh1106(async = "Sh1106Async"),
+        )
+    )
+)]
+#[derive(Debug)]
+pub struct RawModeGuard<'a, DI, SIZE, SHADOW, BUF>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    display: &'a mut Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync"),
+            Command(async = "CommandAsync"),
+            DisplaySize(async = "DisplaySizeAsync"),
+            RawModeGuard(async = "RawModeGuardAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            Sh1106(async = "Sh1106Async"),
+        )
+    )
+)]
+impl<DI, SIZE, SHADOW, BUF> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Write to the display RAM directly for as long as the returned guard lives, for example for
+    /// a quick bulk upload of a splash screen, without leaving the buffered graphics mode.
+    ///
+    /// The next [`flush`](Sh1106::flush) after the guard is dropped sends the whole framebuffer.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let interface = MockInterface::new();
+    /// use sh1106::{command::Page, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// {
+    ///     let mut raw = display.as_raw();
+    ///
+    ///     raw.set_page(Page::Page2).unwrap();
+    ///     raw.set_column(0).unwrap();
+    ///     raw.draw(&[0xFF; 128]).unwrap();
+    /// }
+    ///
+    /// // The framebuffer wasn't drawn to, but is sent in full to replace the raw writes
+    /// assert!(display.buffer().iter().all(|byte| *byte == 0));
+    /// assert!(display.is_dirty());
+    ///
+    /// display.flush().unwrap();
+    ///
+    /// let interface = display.release();
+    /// assert_eq!(interface.data().len(), 128 + 128 * 64 / 8);
+    /// ```
+    pub fn as_raw(&mut self) -> RawModeGuard<'_, DI, SIZE, SHADOW, BUF> {
+        RawModeGuard { display: self }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync"),
+            Command(async = "CommandAsync"),
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+        )
+    )
+)]
+impl<DI, SIZE, SHADOW, BUF> RawModeGuard<'_, DI, SIZE, SHADOW, BUF>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Set the page (8px high row) of the display RAM where any sent data should be drawn.
+    pub async fn set_page(&mut self, page: Page) -> Result<(), Error> {
+        Command::PageStart(page)
+            .send(&mut self.display.interface)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set the column where any sent data should be drawn, relative to the visible area of the
+    /// display, see [`Sh1106::set_column`].
+    pub async fn set_column(&mut self, column: u8) -> Result<(), Error> {
+        self.display.set_column(column).await
+    }
+
+    /// Send raw data to the display at the current page and column, see [`Sh1106::draw`].
+    pub async fn draw(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.display.draw(data).await
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync"),
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+        )
+    )
+)]
+impl<DI, SIZE, SHADOW, BUF> Drop for RawModeGuard<'_, DI, SIZE, SHADOW, BUF>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    fn drop(&mut self) {
+        self.display.mark_all_dirty();
+    }
+}
@@ -0,0 +1,257 @@
+//! Seven-segment digit rendering for the buffered graphics mode.
+
+#[cfg(feature = "async")]
+use crate::{mode::BufferedGraphicsModeAsync, size::DisplaySizeAsync, Sh1106Async};
+use crate::{
+    mode::{BufferedGraphicsMode, Shadow},
+    size::DisplaySize,
+    Sh1106,
+};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::WriteOnlyDataCommand;
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+};
+
+/// Segment A (top)
+const SEG_A: u8 = 1 << 0;
+/// Segment B (top right)
+const SEG_B: u8 = 1 << 1;
+/// Segment C (bottom right)
+const SEG_C: u8 = 1 << 2;
+/// Segment D (bottom)
+const SEG_D: u8 = 1 << 3;
+/// Segment E (bottom left)
+const SEG_E: u8 = 1 << 4;
+/// Segment F (top left)
+const SEG_F: u8 = 1 << 5;
+/// Segment G (middle)
+const SEG_G: u8 = 1 << 6;
+
+/// Size of the digits drawn by [`draw_seven_segment`](Sh1106::draw_seven_segment).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SevenSegSize {
+    width: u32,
+    height: u32,
+    thickness: u32,
+}
+
+impl SevenSegSize {
+    /// 8x14px digits with 2px segments
+    pub const SMALL: SevenSegSize = SevenSegSize::new(8, 14, 2);
+
+    /// 12x22px digits with 3px segments
+    pub const MEDIUM: SevenSegSize = SevenSegSize::new(12, 22, 3);
+
+    /// 20x40px digits with 4px segments
+    pub const LARGE: SevenSegSize = SevenSegSize::new(20, 40, 4);
+
+    /// Create a digit size from a digit width, height and segment thickness, all in pixels.
+    ///
+    /// The thickness is clamped so that segments always fit within the digit.
+    pub const fn new(width: u32, height: u32, thickness: u32) -> Self {
+        let max_thickness = (if width < height { width } else { height }) / 3;
+        let thickness = if thickness > max_thickness {
+            max_thickness
+        } else {
+            thickness
+        };
+
+        Self {
+            width,
+            height,
+            thickness: if thickness == 0 { 1 } else { thickness },
+        }
+    }
+
+    /// Create a digit size with the given height, using the proportions of the predefined sizes.
+    ///
+    /// Pass the display height to fill the whole panel vertically.
+    pub const fn from_height(height: u32) -> Self {
+        Self::new(height / 2, height, height / 10)
+    }
+
+    /// The horizontal distance between the start of one digit and the next.
+    pub const fn advance(&self) -> u32 {
+        self.width + self.thickness
+    }
+
+    fn segments(c: char) -> Option<u8> {
+        let segments = match c {
+            '0' => SEG_A | SEG_B | SEG_C | SEG_D | SEG_E | SEG_F,
+            '1' => SEG_B | SEG_C,
+            '2' => SEG_A | SEG_B | SEG_D | SEG_E | SEG_G,
+            '3' => SEG_A | SEG_B | SEG_C | SEG_D | SEG_G,
+            '4' => SEG_B | SEG_C | SEG_F | SEG_G,
+            '5' => SEG_A | SEG_C | SEG_D | SEG_F | SEG_G,
+            '6' => SEG_A | SEG_C | SEG_D | SEG_E | SEG_F | SEG_G,
+            '7' => SEG_A | SEG_B | SEG_C,
+            '8' => SEG_A | SEG_B | SEG_C | SEG_D | SEG_E | SEG_F | SEG_G,
+            '9' => SEG_A | SEG_B | SEG_C | SEG_D | SEG_F | SEG_G,
+            '-' => SEG_G,
+            ' ' => 0,
+            _ => return None,
+        };
+
+        Some(segments)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync")
+        )
+    )
+)]
+impl<DI, SIZE, SHADOW, BUF> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Draw a string of seven-segment style digits into the framebuffer, with the top left
+    /// corner of the first digit at `at`.
+    ///
+    /// The digits `0`-`9`, `-`, `:` and space are supported. Any other character is drawn as a
+    /// blank digit. Segments which fall outside the display are clipped.
+    ///
+    /// Returns the position at which the next digit would be drawn.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+    /// use sh1106::{mode::SevenSegSize, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.draw_seven_segment(
+    ///     "12:34",
+    ///     Point::new(4, 12),
+    ///     SevenSegSize::LARGE,
+    ///     BinaryColor::On,
+    /// );
+    /// ```
+    ///
+    /// Each byte of the framebuffer holds 8 rows of one column, with the top row in bit 0:
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+    /// use sh1106::{mode::SevenSegSize, prelude::*, Sh1106};
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// let small = SevenSegSize::SMALL;
+    /// let next = display.draw_seven_segment("8-:", Point::zero(), small, BinaryColor::On);
+    ///
+    /// // Two digits 10px apart, and a colon 6px wide
+    /// assert_eq!(next, Point::new(26, 0));
+    ///
+    /// let buffer = display.buffer();
+    /// let column = |x: usize| (buffer[x], buffer[128 + x]);
+    ///
+    /// // The 8 is 14 rows tall, with 2px segments at rows 0-1, 6-7 and 12-13
+    /// assert_eq!(column(0), (0xFF, 0x3F));
+    /// assert_eq!(column(2), (0xC3, 0x30));
+    /// assert_eq!(column(7), (0xFF, 0x3F));
+    /// assert_eq!(column(8), (0x00, 0x00));
+    /// // The dash is only the middle segment
+    /// assert_eq!(column(10), (0xC0, 0x00));
+    /// assert_eq!(column(17), (0xC0, 0x00));
+    /// // The colon is two 2px dots at rows 3-4 and 8-9
+    /// assert_eq!(column(21), (0x00, 0x00));
+    /// assert_eq!(column(22), (0x18, 0x03));
+    /// assert_eq!(column(23), (0x18, 0x03));
+    ///
+    /// // Only the left half of a digit at the right edge is drawn
+    /// let next = display.draw_seven_segment("8", Point::new(124, 16), small, BinaryColor::On);
+    ///
+    /// assert_eq!(next, Point::new(134, 16));
+    ///
+    /// let buffer = display.buffer();
+    /// assert_eq!(&buffer[2 * 128 + 124..3 * 128], &[0xFF, 0xFF, 0xC3, 0xC3]);
+    /// assert_eq!(&buffer[3 * 128 + 124..4 * 128], &[0x3F, 0x3F, 0x30, 0x30]);
+    /// ```
+    pub fn draw_seven_segment(
+        &mut self,
+        value: &str,
+        at: Point,
+        size: SevenSegSize,
+        color: BinaryColor,
+    ) -> Point {
+        let on = color.is_on();
+        let w = size.width as i32;
+        let h = size.height as i32;
+        let t = size.thickness as i32;
+        // Top of the middle segment
+        let mid = (h - t) / 2;
+
+        let mut pos = at;
+
+        for c in value.chars() {
+            if c == ':' {
+                let x = pos.x + t;
+                self.fill_segment(x, pos.y + h / 3 - t / 2, t, t, on);
+                self.fill_segment(x, pos.y + 2 * h / 3 - t / 2, t, t, on);
+
+                pos.x += 3 * t;
+                continue;
+            }
+
+            let segments = SevenSegSize::segments(c).unwrap_or(0);
+            let (x, y) = (pos.x, pos.y);
+
+            if segments & SEG_A != 0 {
+                self.fill_segment(x, y, w, t, on);
+            }
+            if segments & SEG_B != 0 {
+                self.fill_segment(x + w - t, y, t, mid + t, on);
+            }
+            if segments & SEG_C != 0 {
+                self.fill_segment(x + w - t, y + mid, t, h - mid, on);
+            }
+            if segments & SEG_D != 0 {
+                self.fill_segment(x, y + h - t, w, t, on);
+            }
+            if segments & SEG_E != 0 {
+                self.fill_segment(x, y + mid, t, h - mid, on);
+            }
+            if segments & SEG_F != 0 {
+                self.fill_segment(x, y, t, mid + t, on);
+            }
+            if segments & SEG_G != 0 {
+                self.fill_segment(x, y + mid, w, t, on);
+            }
+
+            pos.x += size.advance() as i32;
+        }
+
+        pos
+    }
+
+    /// Fill a rectangle in the framebuffer, clipping it to the display.
+    ///
+    /// The rectangle is written a framebuffer byte at a time, like
+    /// [`fill_solid`](embedded_graphics_core::draw_target::DrawTarget::fill_solid).
+    fn fill_segment(&mut self, x: i32, y: i32, width: i32, height: i32, value: bool) {
+        let area = Rectangle::new(
+            Point::new(x, y),
+            Size::new(width.max(0) as u32, height.max(0) as u32),
+        );
+
+        self.fill_rectangle(area, self.pixel_value(value));
+    }
+}
@@ -1,5 +1,7 @@
+use super::font::{char_to_bitmap, rotate_bitmap};
 #[cfg(feature = "async")]
 use crate::mode::DisplayConfigAsync;
+use crate::Error;
 use crate::{mode::DisplayConfig, rotation::DisplayRotation, size::*, Sh1106};
 #[cfg(feature = "async")]
 use crate::{size::DisplaySizeAsync, Sh1106Async};
@@ -85,6 +87,9 @@ impl TerminalDisplaySize for DisplaySize64x48 {
     const CHAR_NUM: u8 = 48;
 }
 
+/// Number of 8px pages in the SH1106 display RAM
+const RAM_PAGES: u8 = 8;
+
 /// Contains the new row that the cursor has wrapped around to
 struct CursorWrapEvent(u8);
 
@@ -151,7 +156,7 @@ impl Cursor {
 #[derive(Clone)]
 pub enum TerminalModeError {
     /// An error occurred in the underlying interface layer
-    InterfaceError(DisplayError),
+    InterfaceError(Error),
     /// The mode was used before it was initialized
     Uninitialized,
     /// A location was specified outside the bounds of the screen
@@ -168,13 +173,25 @@ impl fmt::Debug for TerminalModeError {
     }
 }
 
+impl From<Error> for TerminalModeError {
+    fn from(value: Error) -> Self {
+        TerminalModeError::InterfaceError(value)
+    }
+}
+
 impl From<DisplayError> for TerminalModeError {
     fn from(value: DisplayError) -> Self {
-        TerminalModeError::InterfaceError(value)
+        TerminalModeError::InterfaceError(value.into())
     }
 }
 
 /// Terminal mode.
+///
+/// A bufferless mode which draws 8x8px characters straight to the display, like a simple
+/// terminal. Text wraps to the next line at the right edge of the display. When the bottom of the
+/// display is reached, all lines are scrolled up by one using the display start line, so no
+/// framebuffer is required. Scrolling is not possible when the display is rotated by 90 or 270
+/// degrees, in which case the cursor wraps back to the top of the display.
 #[maybe_async_cfg::maybe(
     sync(keep_self),
     async(feature = "async", idents(TerminalMode(async = "TerminalModeAsync")))
@@ -182,6 +199,8 @@ impl From<DisplayError> for TerminalModeError {
 #[derive(Debug, Copy, Clone, Default)]
 pub struct TerminalMode {
     cursor: Option<Cursor>,
+    /// The display RAM page shown as the top line of the display
+    top_page: u8,
 }
 
 #[maybe_async_cfg::maybe(
@@ -267,9 +286,9 @@ where
         //)
         //.await?;
 
-        // Clear the display
-        for _ in 0..SIZE::CHAR_NUM {
-            self.draw(&[0; 8]).await?;
+        // Clear the display one page at a time, as the SH1106 only supports page addressing
+        for page in 0..SIZE::HEIGHT / 8 {
+            self.clear_page(page).await?;
         }
 
         self.reset_pos().await
@@ -279,22 +298,19 @@ where
     pub async fn print_char(&mut self, c: char) -> Result<(), TerminalModeError> {
         match c {
             '\n' => {
-                let CursorWrapEvent(new_line) = self.ensure_cursor()?.advance_line();
-                self.set_position(0, new_line).await?;
+                let wrap = self.ensure_cursor()?.advance_line();
+                self.handle_wrap(Some(wrap)).await?;
             }
             '\r' => {
-                self.set_column(0).await?;
                 let (_, cur_line) = self.ensure_cursor()?.get_position();
-                self.ensure_cursor()?.set_position(0, cur_line);
+                self.set_position(0, cur_line).await?;
             }
             _ => {
                 let bitmap = match self.rotation {
-                    DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                        Self::char_to_bitmap(c)
-                    }
+                    DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => char_to_bitmap(c),
                     DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                        let bitmap = Self::char_to_bitmap(c);
-                        Self::rotate_bitmap(bitmap)
+                        let bitmap = char_to_bitmap(c);
+                        rotate_bitmap(bitmap)
                     }
                 };
 
@@ -326,21 +342,14 @@ where
         if column >= width || row >= height {
             Err(TerminalModeError::OutOfBounds)
         } else {
-            let offset_x = match self.rotation() {
-                DisplayRotation::Rotate0 | DisplayRotation::Rotate270 => SIZE::OFFSETX,
-                DisplayRotation::Rotate180 | DisplayRotation::Rotate90 => {
-                    // If segment remapping is flipped, we need to calculate
-                    // the offset from the other edge of the display.
-                    SIZE::DRIVER_COLS - SIZE::WIDTH - SIZE::OFFSETX
-                }
-            };
             match self.rotation() {
                 DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                    self.set_column(offset_x + column * 8).await?;
-                    self.set_row(SIZE::OFFSETY + row * 8).await?;
+                    let page = (self.mode.top_page + row) % RAM_PAGES;
+                    self.set_column(column * 8).await?;
+                    self.set_row(SIZE::OFFSETY + page * 8).await?;
                 }
                 DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                    self.set_column(offset_x + row * 8).await?;
+                    self.set_column(row * 8).await?;
                     self.set_row(SIZE::OFFSETY + column * 8).await?;
                 }
             }
@@ -352,12 +361,13 @@ where
     /// Reset the draw area and move pointer to the top left corner
     async fn reset_pos(&mut self) -> Result<(), TerminalModeError> {
         // Initialise the counter when we know it's valid
-        let (w, h) = match self.rotation() {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (SIZE::WIDTH, SIZE::HEIGHT),
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (SIZE::HEIGHT, SIZE::WIDTH),
-        };
+        let (w, h) = self.dimensions();
         self.mode.cursor = Some(Cursor::new(w, h));
 
+        // Undo any scrolling
+        self.mode.top_page = 0;
+        self.set_start_line(0).await?;
+
         // Reset cursor position
         self.set_position(0, 0).await
     }
@@ -365,237 +375,56 @@ where
     /// Advance the cursor, automatically wrapping lines and/or screens if necessary
     /// Takes in an already-unwrapped cursor to avoid re-unwrapping
     async fn advance_cursor(&mut self) -> Result<(), TerminalModeError> {
-        let cursor = self.ensure_cursor()?;
+        let wrap = self.ensure_cursor()?.advance();
+        self.handle_wrap(wrap).await
+    }
 
-        cursor.advance();
-        let (c, r) = cursor.get_position();
+    /// Move the display pointer to the cursor after it has moved, scrolling the display if the
+    /// cursor has wrapped past the bottom of the screen.
+    async fn handle_wrap(
+        &mut self,
+        wrap: Option<CursorWrapEvent>,
+    ) -> Result<(), TerminalModeError> {
+        let can_scroll = matches!(
+            self.rotation(),
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180
+        );
+
+        if let (Some(CursorWrapEvent(0)), true) = (wrap, can_scroll) {
+            let (_, height) = self.ensure_cursor()?.get_dimensions();
+
+            // Show the next display RAM page at the bottom of the screen, and clear it for the
+            // new line
+            self.mode.top_page = (self.mode.top_page + 1) % RAM_PAGES;
+            self.set_start_line(self.mode.top_page * 8).await?;
+            self.clear_page((self.mode.top_page + height - 1) % RAM_PAGES)
+                .await?;
+
+            self.ensure_cursor()?.set_position(0, height - 1);
+        }
+
+        let (c, r) = self.ensure_cursor()?.get_position();
         self.set_position(c, r).await
     }
 
+    /// Clear a single page of the display RAM
+    async fn clear_page(&mut self, page: u8) -> Result<(), TerminalModeError> {
+        self.set_column(0).await?;
+        self.set_row(SIZE::OFFSETY + page * 8).await?;
+
+        for _ in 0..SIZE::WIDTH / 8 {
+            self.draw(&[0; 8]).await?;
+        }
+
+        Ok(())
+    }
+
     fn ensure_cursor(&mut self) -> Result<&mut Cursor, TerminalModeError> {
         self.mode
             .cursor
             .as_mut()
             .ok_or(TerminalModeError::Uninitialized)
     }
-
-    fn char_to_bitmap(input: char) -> [u8; 8] {
-        const CHARS: [[u8; 6]; 95] = [
-            // !
-            [0x00, 0x2f, 0x00, 0x00, 0x00, 0x00],
-            // "
-            [0x03, 0x00, 0x03, 0x00, 0x00, 0x00],
-            // #
-            [0x12, 0x3f, 0x12, 0x12, 0x3f, 0x12],
-            // $
-            [0x2e, 0x2a, 0x7f, 0x2a, 0x3a, 0x00],
-            // %
-            [0x23, 0x13, 0x08, 0x04, 0x32, 0x31],
-            // &
-            [0x10, 0x2a, 0x25, 0x2a, 0x10, 0x20],
-            // '
-            [0x02, 0x01, 0x00, 0x00, 0x00, 0x00],
-            // (
-            [0x1e, 0x21, 0x00, 0x00, 0x00, 0x00],
-            // )
-            [0x21, 0x1e, 0x00, 0x00, 0x00, 0x00],
-            // *
-            [0x08, 0x2a, 0x1c, 0x2a, 0x08, 0x00],
-            // +
-            [0x08, 0x08, 0x3e, 0x08, 0x08, 0x00],
-            // ,
-            [0x80, 0x60, 0x00, 0x00, 0x00, 0x00],
-            // -
-            [0x08, 0x08, 0x08, 0x08, 0x08, 0x00],
-            // .
-            [0x30, 0x30, 0x00, 0x00, 0x00, 0x00],
-            // /
-            [0x20, 0x10, 0x08, 0x04, 0x02, 0x00],
-            // 0
-            [0x1e, 0x31, 0x29, 0x25, 0x23, 0x1e],
-            // 1
-            [0x22, 0x21, 0x3f, 0x20, 0x20, 0x20],
-            // 2
-            [0x32, 0x29, 0x29, 0x29, 0x29, 0x26],
-            // 3
-            [0x12, 0x21, 0x21, 0x25, 0x25, 0x1a],
-            // 4
-            [0x18, 0x14, 0x12, 0x3f, 0x10, 0x00],
-            // 5
-            [0x17, 0x25, 0x25, 0x25, 0x25, 0x19],
-            // 6
-            [0x1e, 0x25, 0x25, 0x25, 0x25, 0x18],
-            // 7
-            [0x01, 0x01, 0x31, 0x09, 0x05, 0x03],
-            // 8
-            [0x1a, 0x25, 0x25, 0x25, 0x25, 0x1a],
-            // 9
-            [0x06, 0x29, 0x29, 0x29, 0x29, 0x1e],
-            // :
-            [0x24, 0x00, 0x00, 0x00, 0x00, 0x00],
-            // ;
-            [0x80, 0x64, 0x00, 0x00, 0x00, 0x00],
-            // <
-            [0x08, 0x14, 0x22, 0x00, 0x00, 0x00],
-            // =
-            [0x14, 0x14, 0x14, 0x14, 0x14, 0x00],
-            // >
-            [0x22, 0x14, 0x08, 0x00, 0x00, 0x00],
-            // ?
-            [0x02, 0x01, 0x01, 0x29, 0x05, 0x02],
-            // @
-            [0x1e, 0x21, 0x2d, 0x2b, 0x2d, 0x0e],
-            // A
-            [0x3e, 0x09, 0x09, 0x09, 0x09, 0x3e],
-            // B
-            [0x3f, 0x25, 0x25, 0x25, 0x25, 0x1a],
-            // C
-            [0x1e, 0x21, 0x21, 0x21, 0x21, 0x12],
-            // D
-            [0x3f, 0x21, 0x21, 0x21, 0x12, 0x0c],
-            // E
-            [0x3f, 0x25, 0x25, 0x25, 0x25, 0x21],
-            // F
-            [0x3f, 0x05, 0x05, 0x05, 0x05, 0x01],
-            // G
-            [0x1e, 0x21, 0x21, 0x21, 0x29, 0x1a],
-            // H
-            [0x3f, 0x04, 0x04, 0x04, 0x04, 0x3f],
-            // I
-            [0x21, 0x21, 0x3f, 0x21, 0x21, 0x00],
-            // J
-            [0x10, 0x20, 0x20, 0x20, 0x20, 0x1f],
-            // K
-            [0x3f, 0x04, 0x0c, 0x0a, 0x11, 0x20],
-            // L
-            [0x3f, 0x20, 0x20, 0x20, 0x20, 0x20],
-            // M
-            [0x3f, 0x02, 0x04, 0x04, 0x02, 0x3f],
-            // N
-            [0x3f, 0x02, 0x04, 0x08, 0x10, 0x3f],
-            // O
-            [0x1e, 0x21, 0x21, 0x21, 0x21, 0x1e],
-            // P
-            [0x3f, 0x09, 0x09, 0x09, 0x09, 0x06],
-            // Q
-            [0x1e, 0x21, 0x29, 0x31, 0x21, 0x5e],
-            // R
-            [0x3f, 0x09, 0x09, 0x09, 0x19, 0x26],
-            // S
-            [0x12, 0x25, 0x25, 0x25, 0x25, 0x18],
-            // T
-            [0x01, 0x01, 0x01, 0x3f, 0x01, 0x01],
-            // U
-            [0x1f, 0x20, 0x20, 0x20, 0x20, 0x1f],
-            // V
-            [0x0f, 0x10, 0x20, 0x20, 0x10, 0x0f],
-            // W
-            [0x1f, 0x20, 0x10, 0x10, 0x20, 0x1f],
-            // X
-            [0x21, 0x12, 0x0c, 0x0c, 0x12, 0x21],
-            // Y
-            [0x01, 0x02, 0x3c, 0x02, 0x01, 0x00],
-            // Z
-            [0x21, 0x31, 0x29, 0x25, 0x23, 0x21],
-            // [
-            [0x3f, 0x21, 0x00, 0x00, 0x00, 0x00],
-            // \
-            [0x02, 0x04, 0x08, 0x10, 0x20, 0x00],
-            // ]
-            [0x21, 0x3f, 0x00, 0x00, 0x00, 0x00],
-            // ^
-            [0x04, 0x02, 0x3f, 0x02, 0x04, 0x00],
-            // _
-            [0x40, 0x40, 0x40, 0x40, 0x40, 0x40],
-            // `
-            [0x01, 0x02, 0x00, 0x00, 0x00, 0x00],
-            // a
-            [0x10, 0x2a, 0x2a, 0x2a, 0x3c, 0x00],
-            // b
-            [0x3f, 0x24, 0x24, 0x24, 0x18, 0x00],
-            // c
-            [0x1c, 0x22, 0x22, 0x22, 0x00, 0x00],
-            // d
-            [0x18, 0x24, 0x24, 0x24, 0x3f, 0x00],
-            // e
-            [0x1c, 0x2a, 0x2a, 0x2a, 0x24, 0x00],
-            // f
-            [0x00, 0x3e, 0x05, 0x01, 0x00, 0x00],
-            // g
-            [0x18, 0xa4, 0xa4, 0xa4, 0x7c, 0x00],
-            // h
-            [0x3f, 0x04, 0x04, 0x04, 0x38, 0x00],
-            // i
-            [0x00, 0x24, 0x3d, 0x20, 0x00, 0x00],
-            // j
-            [0x20, 0x40, 0x40, 0x3d, 0x00, 0x00],
-            // k
-            [0x3f, 0x0c, 0x12, 0x20, 0x00, 0x00],
-            // l
-            [0x1f, 0x20, 0x20, 0x00, 0x00, 0x00],
-            // m
-            [0x3e, 0x02, 0x3c, 0x02, 0x3c, 0x00],
-            // n
-            [0x3e, 0x02, 0x02, 0x02, 0x3c, 0x00],
-            // o
-            [0x1c, 0x22, 0x22, 0x22, 0x1c, 0x00],
-            // p
-            [0xfc, 0x24, 0x24, 0x24, 0x18, 0x00],
-            // q
-            [0x18, 0x24, 0x24, 0x24, 0xfc, 0x00],
-            // r
-            [0x3e, 0x04, 0x02, 0x02, 0x00, 0x00],
-            // s
-            [0x24, 0x2a, 0x2a, 0x2a, 0x10, 0x00],
-            // t
-            [0x02, 0x1f, 0x22, 0x20, 0x00, 0x00],
-            // u
-            [0x1e, 0x20, 0x20, 0x20, 0x1e, 0x00],
-            // v
-            [0x06, 0x18, 0x20, 0x18, 0x06, 0x00],
-            // w
-            [0x1e, 0x30, 0x1c, 0x30, 0x1e, 0x00],
-            // x
-            [0x22, 0x14, 0x08, 0x14, 0x22, 0x00],
-            // y
-            [0x1c, 0xa0, 0xa0, 0xa0, 0x7c, 0x00],
-            // z
-            [0x22, 0x32, 0x2a, 0x26, 0x22, 0x00],
-            // {
-            [0x0c, 0x3f, 0x21, 0x00, 0x00, 0x00],
-            // |
-            [0x3f, 0x00, 0x00, 0x00, 0x00, 0x00],
-            // }
-            [0x21, 0x3f, 0x0c, 0x00, 0x00, 0x00],
-            // ~
-            [0x02, 0x01, 0x02, 0x01, 0x00, 0x00],
-            // blank
-            [0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
-        ];
-
-        let g = (input as usize)
-            .checked_sub(b'!'.into())
-            .and_then(|idx| CHARS.get(idx))
-            .unwrap_or(&CHARS[CHARS.len() - 1]);
-
-        [0, g[0], g[1], g[2], g[3], g[4], g[5], 0]
-    }
-
-    fn rotate_bitmap(bitmap: [u8; 8]) -> [u8; 8] {
-        let mut rotated: [u8; 8] = [0; 8];
-
-        for (col, source) in bitmap.iter().enumerate() {
-            // source.msb is the top pixel
-            for (row, item) in rotated.iter_mut().enumerate() {
-                let bit = source & 1 << row != 0;
-                if bit {
-                    *item |= 1 << col;
-                }
-            }
-        }
-
-        rotated
-    }
 }
 
 #[cfg(feature = "async")]
@@ -0,0 +1,147 @@
+//! Draw targets for a part of the display.
+
+#[cfg(feature = "async")]
+use crate::{mode::BufferedGraphicsModeAsync, size::DisplaySizeAsync, Sh1106Async};
+use crate::{
+    mode::{BufferedGraphicsMode, Shadow},
+    size::DisplaySize,
+    Sh1106,
+};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::WriteOnlyDataCommand;
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+/// A draw target for a rectangular area of another draw target, with its own origin.
+///
+/// Coordinates drawn to the viewport are relative to the top left corner of the area, and
+/// anything outside of the area is clipped. This allows drawing widgets into independent panels
+/// of the display without offsetting every coordinate. Create one with
+/// [`Sh1106::viewport`], or [`Viewport::new`] for any other draw target.
+///
+/// ```rust
+/// # use sh1106::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use embedded_graphics::{
+///     pixelcolor::BinaryColor,
+///     prelude::*,
+///     primitives::{Line, PrimitiveStyle, Rectangle},
+/// };
+/// use sh1106::{prelude::*, Sh1106};
+///
+/// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// let mut viewport = display.viewport(Rectangle::new(Point::new(10, 8), Size::new(4, 8)));
+/// assert_eq!(viewport.bounding_box(), Rectangle::new(Point::zero(), Size::new(4, 8)));
+///
+/// // A line far wider than the viewport only draws its first 4 pixels
+/// Line::new(Point::new(0, 0), Point::new(100, 0))
+///     .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+///     .draw(&mut viewport)
+///     .unwrap();
+///
+/// let page = &display.buffer()[128..256];
+/// assert_eq!(&page[8..16], &[0, 0, 1, 1, 1, 1, 0, 0]);
+/// assert!(display.buffer()[..128].iter().all(|byte| *byte == 0));
+/// ```
+#[derive(Debug)]
+pub struct Viewport<'a, T> {
+    target: &'a mut T,
+    area: Rectangle,
+    /// The part of `area` inside the target
+    clip: Rectangle,
+}
+
+impl<'a, T> Viewport<'a, T>
+where
+    T: DrawTarget<Color = BinaryColor>,
+{
+    /// Create a viewport for `area` of `target`.
+    ///
+    /// The viewport keeps the origin and size of `area`, even if part of it is outside of
+    /// `target`. Drawing to those parts is clipped.
+    pub fn new(target: &'a mut T, area: Rectangle) -> Self {
+        let clip = area.intersection(&target.bounding_box());
+
+        Self { target, area, clip }
+    }
+
+    /// Get the area of the parent draw target covered by the viewport.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl<T> OriginDimensions for Viewport<'_, T>
+where
+    T: DrawTarget<Color = BinaryColor>,
+{
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}
+
+impl<T> DrawTarget for Viewport<'_, T>
+where
+    T: DrawTarget<Color = BinaryColor>,
+{
+    type Color = BinaryColor;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (origin, clip) = (self.area.top_left, self.clip);
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(pos, color)| Pixel(pos + origin, color))
+                .filter(|Pixel(pos, _)| clip.contains(*pos)),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area =
+            Rectangle::new(area.top_left + self.area.top_left, area.size).intersection(&self.clip);
+
+        if area.size.width == 0 || area.size.height == 0 {
+            return Ok(());
+        }
+
+        self.target.fill_solid(&area, color)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            BufferedGraphicsMode(async = "BufferedGraphicsModeAsync")
+        )
+    )
+)]
+impl<DI, SIZE, SHADOW, BUF> Sh1106<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW, BUF>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: Shadow,
+    BUF: AsMut<[u8]> + AsRef<[u8]>,
+{
+    /// Get a [`Viewport`] drawing into `area` of the framebuffer, with its origin at the top
+    /// left corner of `area`. Drawing outside of `area` or the display is clipped.
+    pub fn viewport(&mut self, area: Rectangle) -> Viewport<'_, Self> {
+        Viewport::new(self, area)
+    }
+}
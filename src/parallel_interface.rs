@@ -0,0 +1,91 @@
+//! 8080 parallel interface
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use display_interface_parallel_gpio::{OutputBus, PGPIO8BitInterface};
+use embedded_hal::digital::OutputPin;
+
+/// An 8080-style 8 bit parallel interface driven by GPIO pins, for breakouts which expose the
+/// parallel bus of the SH1106.
+///
+/// A whole byte is written per WR strobe, so this is much faster than 400kHz I2C for full-frame
+/// animations. It needs 11 GPIO pins: the 8 data pins D0 to D7, wrapped in a
+/// [`Generic8BitBus`](crate::prelude::Generic8BitBus) or another `OutputBus`, and the D/C, WR and
+/// CS pins. The RD pin must be tied high, as the interface never reads, and the RES pin can be
+/// driven by [`reset`](crate::Sh1106::reset) or tied high with a reset circuit.
+///
+/// CS is pulled low for each command or data transfer and released afterwards. This is
+/// available with the `parallel` feature. The interface is blocking, so it can't be used with
+/// the async driver.
+///
+/// ```rust
+/// # use sh1106::test_helpers::PinStub;
+/// # let [d0, d1, d2, d3, d4, d5, d6, d7, dc, wr, cs] = [PinStub; 11];
+/// use sh1106::{prelude::*, ParallelInterface, Sh1106};
+///
+/// let bus = Generic8BitBus::new((d0, d1, d2, d3, d4, d5, d6, d7));
+/// let interface = ParallelInterface::new(bus, dc, wr, cs);
+///
+/// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// display.init().unwrap();
+/// display.flush().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ParallelInterface<BUS, DC, WR, CS> {
+    interface: PGPIO8BitInterface<BUS, DC, WR>,
+    cs: CS,
+}
+
+impl<BUS, DC, WR, CS> ParallelInterface<BUS, DC, WR, CS>
+where
+    BUS: OutputBus<Word = u8>,
+    DC: OutputPin,
+    WR: OutputPin,
+    CS: OutputPin,
+{
+    /// Create a new parallel interface from the data bus and the D/C, WR and CS pins.
+    pub fn new(bus: BUS, dc: DC, wr: WR, cs: CS) -> Self {
+        Self {
+            interface: PGPIO8BitInterface::new(bus, dc, wr),
+            cs,
+        }
+    }
+
+    /// Release the data bus and the D/C, WR and CS pins.
+    pub fn release(self) -> (BUS, DC, WR, CS) {
+        let (bus, dc, wr) = self.interface.release();
+
+        (bus, dc, wr, self.cs)
+    }
+
+    /// Run `transfer` with CS pulled low, releasing CS again even if the transfer fails.
+    ///
+    /// An error of the transfer is returned rather than an error releasing CS after it.
+    fn selected(
+        &mut self,
+        transfer: impl FnOnce(&mut PGPIO8BitInterface<BUS, DC, WR>) -> Result<(), DisplayError>,
+    ) -> Result<(), DisplayError> {
+        self.cs.set_low().map_err(|_| DisplayError::CSError)?;
+        let result = transfer(&mut self.interface);
+        let released = self.cs.set_high().map_err(|_| DisplayError::CSError);
+
+        result.and(released)
+    }
+}
+
+impl<BUS, DC, WR, CS> WriteOnlyDataCommand for ParallelInterface<BUS, DC, WR, CS>
+where
+    BUS: OutputBus<Word = u8>,
+    DC: OutputPin,
+    WR: OutputPin,
+    CS: OutputPin,
+{
+    fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.selected(|interface| interface.send_commands(cmds))
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.selected(|interface| interface.send_data(buf))
+    }
+}
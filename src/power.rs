@@ -0,0 +1,285 @@
+//! Idle power management.
+//!
+//! [`PowerPolicy`] dims the display after a period of inactivity and restores full contrast when
+//! activity is reported again. It can optionally turn the display off entirely after a second,
+//! longer timeout.
+//!
+//! The policy does not measure time itself. The application reports elapsed time and activity:
+//!
+//! ```rust
+//! # use sh1106::test_helpers::{StubInterface, DelayStub};
+//! # let interface = StubInterface;
+//! # let mut delay = DelayStub;
+//! use sh1106::{power::PowerPolicy, prelude::*, Sh1106};
+//!
+//! let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+//!
+//! // Dim to 0x10 after 5s idle, turn off after 30s idle
+//! let mut policy = PowerPolicy::new(5_000, 0x10, 0xFF).with_off_timeout(30_000);
+//!
+//! // In the main loop, every 100ms or so:
+//! # let button_pressed = false;
+//! if button_pressed {
+//!     policy.notify_activity();
+//! }
+//! policy.tick(&mut display, 100, &mut delay).unwrap();
+//! ```
+
+use crate::Error;
+use crate::{size::DisplaySize, Sh1106};
+#[cfg(feature = "async")]
+use crate::{size::DisplaySizeAsync, Sh1106Async};
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::WriteOnlyDataCommand;
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as DelayNsAsync;
+
+/// Contrast change applied per ramp step.
+const RAMP_STEP: u8 = 8;
+
+/// Current state of a [`PowerPolicy`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PowerState {
+    /// The display is on at full contrast
+    Full,
+    /// The display is on at the dimmed contrast
+    Dimmed,
+    /// The display has been turned off
+    Off,
+}
+
+/// Dim-on-idle power policy.
+///
+/// Call [`notify_activity`](PowerPolicy::notify_activity) whenever the user interacts with the
+/// device, and [`tick`](PowerPolicy::tick) periodically with the time elapsed since the last
+/// tick. The policy only changes the display contrast and, if an off timeout is configured, the
+/// display on/off state. The display contents are never modified.
+#[maybe_async_cfg::maybe(sync(keep_self), async(feature = "async"))]
+#[derive(Debug, Copy, Clone)]
+pub struct PowerPolicy {
+    idle_timeout_ms: u32,
+    off_timeout_ms: Option<u32>,
+    dim_contrast: u8,
+    full_contrast: u8,
+    idle_ms: u32,
+    activity: bool,
+    state: PowerState,
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"),
+            DelayNs(async = "DelayNsAsync"),
+            Sh1106(async = "Sh1106Async"),
+        )
+    )
+)]
+impl PowerPolicy {
+    /// Create a new power policy.
+    ///
+    /// The display is dimmed to `dim_contrast` after `idle_timeout_ms` milliseconds without
+    /// activity, and restored to `full_contrast` on the next activity.
+    pub fn new(idle_timeout_ms: u32, dim_contrast: u8, full_contrast: u8) -> Self {
+        Self {
+            idle_timeout_ms,
+            off_timeout_ms: None,
+            dim_contrast,
+            full_contrast,
+            idle_ms: 0,
+            activity: false,
+            state: PowerState::Full,
+        }
+    }
+
+    /// Additionally turn the display off after `off_timeout_ms` milliseconds without activity.
+    ///
+    /// The timeout is measured from the last activity, not from the moment the display was
+    /// dimmed. It should be longer than the idle timeout.
+    pub fn with_off_timeout(self, off_timeout_ms: u32) -> Self {
+        Self {
+            off_timeout_ms: Some(off_timeout_ms),
+            ..self
+        }
+    }
+
+    /// Get the current state of the policy.
+    pub fn state(&self) -> PowerState {
+        self.state
+    }
+
+    /// Report user activity. Full contrast is restored on the next call to
+    /// [`tick`](PowerPolicy::tick).
+    pub fn notify_activity(&mut self) {
+        self.activity = true;
+    }
+
+    /// Advance the policy by `elapsed_ms` milliseconds, dimming, restoring or turning off the
+    /// display as required.
+    ///
+    /// `delay` is used to pace the contrast ramp when dimming or restoring the display.
+    pub async fn tick<DI, SIZE, MODE, DELAY>(
+        &mut self,
+        display: &mut Sh1106<DI, SIZE, MODE>,
+        elapsed_ms: u32,
+        delay: &mut DELAY,
+    ) -> Result<(), Error>
+    where
+        DI: WriteOnlyDataCommand,
+        SIZE: DisplaySize,
+        DELAY: DelayNs,
+    {
+        if self.activity {
+            self.activity = false;
+            self.idle_ms = 0;
+
+            if self.state == PowerState::Off {
+                display.set_display_on(true).await?;
+            }
+
+            if self.state != PowerState::Full {
+                display
+                    .fade_to(self.full_contrast, RAMP_STEP, delay)
+                    .await?;
+                self.state = PowerState::Full;
+            }
+
+            return Ok(());
+        }
+
+        self.idle_ms = self.idle_ms.saturating_add(elapsed_ms);
+
+        if self.state == PowerState::Full && self.idle_ms >= self.idle_timeout_ms {
+            display.fade_to(self.dim_contrast, RAMP_STEP, delay).await?;
+            self.state = PowerState::Dimmed;
+        }
+
+        if let Some(off_timeout_ms) = self.off_timeout_ms {
+            if self.state == PowerState::Dimmed && self.idle_ms >= off_timeout_ms {
+                display.set_display_on(false).await?;
+                self.state = PowerState::Off;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Display driver which turns the display off when it is dropped, returned by
+/// [`Sh1106::off_on_drop`].
+///
+/// It dereferences to the wrapped driver, so it is used like the driver itself. When it is
+/// dropped, for example at the end of a scope which reconfigures the display, it sends
+/// `DisplayOn(false)` so the panel doesn't keep showing stale content and drawing current.
+///
+/// The command is sent from `Drop`, which is bus activity hidden from the code dropping the
+/// driver, and `Drop` can't return errors, so turning the display off is best effort and a
+/// failure is ignored. This is why it is opt-in. Use
+/// [`set_display_on`](Sh1106::set_display_on) or [`sleep`](Sh1106::sleep) instead when errors
+/// have to be handled, or where the bus may be in use by someone else when the driver is dropped.
+/// `Drop` can't wait for an async transfer, so this is only available for the blocking driver.
+///
+/// ```rust
+/// use sh1106::{prelude::*, test_helpers::MockInterface, Sh1106};
+///
+/// let mut interface = MockInterface::new();
+///
+/// {
+///     let mut display = Sh1106::new(&mut interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///         .into_buffered_graphics_mode()
+///         .off_on_drop();
+///
+///     display.init().unwrap();
+///     display.flush().unwrap();
+/// }
+///
+/// // The display was turned off when the driver went out of scope
+/// assert_eq!(interface.commands().last(), Some(&0xAE));
+///
+/// // into_inner gives the driver back without turning the display off
+/// let display = Sh1106::new(MockInterface::new(), DisplaySize128x64, DisplayRotation::Rotate0)
+///     .off_on_drop()
+///     .into_inner();
+/// assert!(display.release().commands().is_empty());
+/// ```
+#[derive(Debug)]
+pub struct OffOnDrop<DI, SIZE, MODE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// The driver, only taken out by `into_inner`
+    display: Option<Sh1106<DI, SIZE, MODE>>,
+}
+
+impl<DI, SIZE, MODE> OffOnDrop<DI, SIZE, MODE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// Get the wrapped driver back, without turning the display off.
+    pub fn into_inner(mut self) -> Sh1106<DI, SIZE, MODE> {
+        self.display
+            .take()
+            .expect("the driver is only taken by into_inner")
+    }
+}
+
+impl<DI, SIZE, MODE> Deref for OffOnDrop<DI, SIZE, MODE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    type Target = Sh1106<DI, SIZE, MODE>;
+
+    fn deref(&self) -> &Self::Target {
+        self.display
+            .as_ref()
+            .expect("the driver is only taken by into_inner")
+    }
+}
+
+impl<DI, SIZE, MODE> DerefMut for OffOnDrop<DI, SIZE, MODE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.display
+            .as_mut()
+            .expect("the driver is only taken by into_inner")
+    }
+}
+
+impl<DI, SIZE, MODE> Drop for OffOnDrop<DI, SIZE, MODE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    fn drop(&mut self) {
+        if let Some(display) = self.display.as_mut() {
+            // Best effort, there is nobody to return an error to
+            let _ = display.set_display_on(false);
+        }
+    }
+}
+
+impl<DI, SIZE, MODE> Sh1106<DI, SIZE, MODE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// Wrap the driver so the display is turned off when it is dropped. See [`OffOnDrop`] for the
+    /// tradeoffs of sending a command from `Drop`.
+    pub fn off_on_drop(self) -> OffOnDrop<DI, SIZE, MODE> {
+        OffOnDrop {
+            display: Some(self),
+        }
+    }
+}
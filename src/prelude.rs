@@ -4,14 +4,22 @@ pub use display_interface::WriteOnlyDataCommand;
 pub use display_interface_i2c::I2CInterface;
 pub use display_interface_spi::SPIInterface;
 
+#[cfg(feature = "parallel")]
+pub use display_interface_parallel_gpio::Generic8BitBus;
+
 pub use super::{
     brightness::Brightness,
-    mode::DisplayConfig,
+    config::{DisplayConfigState, InitCommands, InitConfig, PanelProfile},
+    mode::{
+        DisplayConfig, Sh1106Buffered, Sh1106_128x32, Sh1106_128x64, Sh1106_64x32, Sh1106_64x48,
+        Sh1106_72x40, Sh1106_96x16,
+    },
     rotation::DisplayRotation,
     size::{
         DisplaySize, DisplaySize128x32, DisplaySize128x64, DisplaySize64x48, DisplaySize72x40,
         DisplaySize96x16,
     },
+    SPIDisplayInterface,
 };
 
 #[cfg(feature = "async")]
@@ -0,0 +1,111 @@
+//! Read-capable display interfaces
+
+use crate::{size::DisplaySize, Error, Sh1106};
+#[cfg(feature = "async")]
+use crate::{size::DisplaySizeAsync, Sh1106Async};
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{DisplayError, WriteOnlyDataCommand};
+
+/// A display interface which can also read back the display RAM.
+///
+/// The SH1106 only allows reading the display RAM over the parallel and 3-wire SPI buses, so none
+/// of the interfaces in `display-interface` implement this. It is used by
+/// [`flush_rmw`](crate::Sh1106::flush_rmw) to change only some of the pixels in a page without
+/// clobbering the pixels around them.
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"))
+    )
+)]
+pub trait RmwInterface: WriteOnlyDataCommand {
+    /// Read display data bytes into `buf`, exactly as they are clocked out of the controller.
+    ///
+    /// Implementations don't need to skip the dummy read the controller sends after the address
+    /// is set, as the driver does this itself.
+    async fn read_data(&mut self, buf: &mut [u8]) -> Result<(), DisplayError>;
+}
+
+/// A display interface which can read the status byte of the controller.
+///
+/// The status byte is read with the D/C line low, over the parallel and read-capable 3-wire SPI
+/// buses. None of the interfaces in `display-interface`, including the I2C and 4-wire SPI
+/// interfaces usually used with this crate, can read, so [`read_status`](crate::Sh1106::read_status)
+/// is only available with an interface implementing this trait, for example a custom parallel
+/// interface.
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(WriteOnlyDataCommand(async = "AsyncWriteOnlyDataCommand"))
+    )
+)]
+pub trait ReadableInterface: WriteOnlyDataCommand {
+    /// Read the status byte of the controller.
+    async fn read_status(&mut self) -> Result<u8, DisplayError>;
+}
+
+/// Status of the controller, returned by [`Sh1106::read_status`](crate::Sh1106::read_status).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DisplayStatus {
+    /// The controller is busy, for example with a reset, and ignores commands other than a status
+    /// read.
+    pub busy: bool,
+    /// The display is turned on.
+    pub display_on: bool,
+}
+
+impl From<u8> for DisplayStatus {
+    /// Decode a status byte, with the busy flag in bit 7 and the inverted on/off flag in bit 6.
+    fn from(status: u8) -> Self {
+        Self {
+            busy: status & 0x80 != 0,
+            display_on: status & 0x40 == 0,
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    sync(keep_self),
+    async(
+        feature = "async",
+        idents(
+            DisplaySize(async = "DisplaySizeAsync"),
+            ReadableInterface(async = "ReadableInterfaceAsync"),
+        )
+    )
+)]
+impl<DI, SIZE, MODE> Sh1106<DI, SIZE, MODE>
+where
+    DI: ReadableInterface,
+    SIZE: DisplaySize,
+{
+    /// Read the busy and on/off flags of the controller.
+    ///
+    /// This needs an interface implementing [`ReadableInterface`]. Polling the busy flag can
+    /// replace the delays after a reset or power-up, as the controller doesn't accept commands
+    /// while it is busy.
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// # let mut interface = MockInterface::new();
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// // Busy, and the display is off
+    /// interface.set_status(0xC0);
+    ///
+    /// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    /// let status = display.read_status().unwrap();
+    ///
+    /// assert!(status.busy);
+    /// assert!(!status.display_on);
+    /// ```
+    pub async fn read_status(&mut self) -> Result<DisplayStatus, Error> {
+        let status = self.interface.read_status().await?;
+
+        Ok(DisplayStatus::from(status))
+    }
+}
@@ -1,7 +1,8 @@
 //! Display rotation.
 
 /// Display rotation.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum DisplayRotation {
     /// No rotation, normal display
     Rotate0,
@@ -1,10 +1,18 @@
 //! Display size.
 
-use super::command::Command;
+#[cfg(feature = "sh1107")]
+use super::addressing::Sh1107Addressing;
 #[cfg(feature = "async")]
 use super::command::CommandAsync;
+use super::{
+    addressing::{AddressingStrategy, PageAddressing},
+    brightness::Brightness,
+    command::Command,
+};
 #[cfg(feature = "async")]
 use display_interface::AsyncWriteOnlyDataCommand;
+#[cfg(feature = "sh1107")]
+use display_interface::DataFormat;
 use display_interface::{DisplayError, WriteOnlyDataCommand};
 
 /// Workaround trait, since `Default` is only implemented to arrays up to 32 of size
@@ -37,21 +45,103 @@ pub trait DisplaySize {
     /// Height in pixels
     const HEIGHT: u8;
 
-    /// Maximum width supported by the display driver
-    const DRIVER_COLS: u8 = 128;
+    /// Maximum width supported by the display driver. The SH1106 has 132 columns of display RAM,
+    /// even though most panels only show 128 of them.
+    const DRIVER_COLS: u8 = 132;
 
     /// Maximum height supported by the display driver
     const DRIVER_ROWS: u8 = 64;
 
-    /// Horizontal offset in pixels
+    /// Horizontal offset in pixels. This is the display RAM column shown in the leftmost column
+    /// of the panel, and can be overridden with
+    /// [`Sh1106::with_column_offset`](crate::Sh1106::with_column_offset).
     const OFFSETX: u8 = 0;
 
-    /// Vertical offset in pixels
+    /// Vertical offset in pixels. This is sent to the display as the display offset during
+    /// initialisation.
     const OFFSETY: u8 = 0;
 
-    /// Size of framebuffer. Because the display is monochrome, this is
+    /// Length of the framebuffer in bytes. Because the display is monochrome, this is
     /// width * height / 8
-    type Buffer: AsMut<[u8]> + NewZeroed;
+    ///
+    /// The framebuffer only covers the visible pixels of the panel. The display RAM columns
+    /// outside of it, such as the 2 columns on each side of a 128 pixel wide panel skipped by
+    /// [`OFFSETX`](DisplaySize::OFFSETX), are not part of the buffer. Use this to size a buffer
+    /// for [`Sh1106::into_buffered_graphics_mode_with_buffer`](crate::Sh1106::into_buffered_graphics_mode_with_buffer):
+    ///
+    /// ```rust
+    /// use sh1106::prelude::*;
+    ///
+    /// static mut BUFFER: [u8; DisplaySize128x64::BUFFER_LEN] = [0; DisplaySize128x64::BUFFER_LEN];
+    ///
+    /// assert_eq!(DisplaySize128x64::BUFFER_LEN, 1024);
+    /// assert_eq!(DisplaySize72x40.buffer_len(), 360);
+    /// ```
+    const BUFFER_LEN: usize = Self::WIDTH as usize * Self::HEIGHT as usize / 8;
+
+    /// Size of framebuffer, an array of [`BUFFER_LEN`](DisplaySize::BUFFER_LEN) bytes
+    type Buffer: AsMut<[u8]> + AsRef<[u8]> + NewZeroed;
+
+    /// Width and height in pixels, without taking the display rotation into account.
+    ///
+    /// See [`Sh1106::dimensions`](crate::Sh1106::dimensions) for the dimensions of a rotated
+    /// display.
+    ///
+    /// ```rust
+    /// use sh1106::prelude::*;
+    ///
+    /// assert_eq!(DisplaySize128x32::dimensions(), (128, 32));
+    /// ```
+    fn dimensions() -> (u8, u8) {
+        (Self::WIDTH, Self::HEIGHT)
+    }
+
+    /// Contrast sent by [`Sh1106::init_default`](crate::Sh1106::init_default).
+    ///
+    /// This is 0x5F, the contrast of the normal [`Brightness`], for most sizes, and 0x7F for the
+    /// small 72x40, 64x48 and 64x32 panels, which look washed out at 0x5F. Change it after
+    /// initialisation with [`Sh1106::set_contrast`](crate::Sh1106::set_contrast).
+    ///
+    /// ```rust
+    /// # use sh1106::test_helpers::MockInterface;
+    /// use sh1106::{prelude::*, Sh1106};
+    ///
+    /// // Get the value of the contrast command sent by init_default
+    /// fn init_contrast<SIZE: DisplaySize>(size: SIZE) -> u8 {
+    ///     let mut display = Sh1106::new(MockInterface::new(), size, DisplayRotation::Rotate0);
+    ///     display.init_default().unwrap();
+    ///
+    ///     let commands = display.release();
+    ///     let commands = commands.commands();
+    ///     let index = commands.iter().position(|byte| *byte == 0x81).unwrap();
+    ///
+    ///     commands[index + 1]
+    /// }
+    ///
+    /// assert_eq!(init_contrast(DisplaySize128x64), 0x5F);
+    /// assert_eq!(init_contrast(DisplaySize128x32), 0x5F);
+    /// assert_eq!(init_contrast(DisplaySize96x16), 0x5F);
+    /// assert_eq!(init_contrast(DisplaySize72x40), 0x7F);
+    /// assert_eq!(init_contrast(DisplaySize64x48), 0x7F);
+    /// assert_eq!(init_contrast(sh1106::size::DisplaySize64x32), 0x7F);
+    /// assert_eq!(DisplaySize72x40::default_contrast(), 0x7F);
+    /// ```
+    fn default_contrast() -> u8 {
+        Brightness::NORMAL.contrast
+    }
+
+    /// Addressing used by flushes unless it is changed with
+    /// [`Sh1106::with_addressing`](crate::Sh1106::with_addressing).
+    ///
+    /// This is [`PageAddressing`] for the SH1106 sizes.
+    fn addressing() -> &'static dyn AddressingStrategy {
+        &PageAddressing
+    }
+
+    /// Get the length of the framebuffer in bytes, see [`BUFFER_LEN`](DisplaySize::BUFFER_LEN).
+    fn buffer_len(&self) -> usize {
+        Self::BUFFER_LEN
+    }
 
     /// Send resolution and model-dependent configuration to the display
     ///
@@ -75,9 +165,9 @@ pub struct DisplaySize128x64;
 #[maybe_async_cfg::maybe(sync(keep_self), async(feature = "async", keep_self))]
 impl DisplaySize for DisplaySize128x64 {
     const WIDTH: u8 = 128;
+    const OFFSETX: u8 = 2;
     const HEIGHT: u8 = 64;
-    type Buffer = [u8; <Self as DisplaySize>::WIDTH as usize *
-        <Self as DisplaySize>::HEIGHT as usize / 8];
+    type Buffer = [u8; <Self as DisplaySize>::BUFFER_LEN];
 
     async fn configure(
         &self,
@@ -88,14 +178,41 @@ impl DisplaySize for DisplaySize128x64 {
 }
 
 /// Size information for the common 128x32 variants
+///
+/// These panels are four pages tall, so the framebuffer of the buffered graphics mode is
+/// 128 * 32 / 8 = 512 bytes. Initialisation sets a multiplex ratio of `0x1F` and sequential COM
+/// pin configuration.
+///
+/// ```rust
+/// # use sh1106::test_helpers::MockInterface;
+/// # let interface = MockInterface::new();
+/// use sh1106::{prelude::*, Sh1106};
+///
+/// assert_eq!(
+///     core::mem::size_of::<<DisplaySize128x32 as DisplaySize>::Buffer>(),
+///     512
+/// );
+///
+/// let mut display = Sh1106::new(interface, DisplaySize128x32, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+/// display.init().unwrap();
+///
+/// let interface = display.release();
+/// let commands = interface.commands();
+///
+/// // Multiplex ratio
+/// assert!(commands.windows(2).any(|c| c == [0xA8, 0x1F]));
+/// // Sequential COM pin configuration
+/// assert!(commands.windows(2).any(|c| c == [0xDA, 0x02]));
+/// ```
 #[derive(Debug, Copy, Clone)]
 pub struct DisplaySize128x32;
 #[maybe_async_cfg::maybe(sync(keep_self), async(feature = "async", keep_self))]
 impl DisplaySize for DisplaySize128x32 {
     const WIDTH: u8 = 128;
+    const OFFSETX: u8 = 2;
     const HEIGHT: u8 = 32;
-    type Buffer = [u8; <Self as DisplaySize>::WIDTH as usize *
-        <Self as DisplaySize>::HEIGHT as usize / 8];
+    type Buffer = [u8; <Self as DisplaySize>::BUFFER_LEN];
 
     async fn configure(
         &self,
@@ -112,8 +229,7 @@ pub struct DisplaySize96x16;
 impl DisplaySize for DisplaySize96x16 {
     const WIDTH: u8 = 96;
     const HEIGHT: u8 = 16;
-    type Buffer = [u8; <Self as DisplaySize>::WIDTH as usize *
-        <Self as DisplaySize>::HEIGHT as usize / 8];
+    type Buffer = [u8; <Self as DisplaySize>::BUFFER_LEN];
 
     async fn configure(
         &self,
@@ -124,6 +240,44 @@ impl DisplaySize for DisplaySize96x16 {
 }
 
 /// Size information for the common 72x40 variants
+///
+/// These panels show a window of 72 of the 132 columns and 40 of the 64 rows of display RAM.
+/// The column offset defaults to 28, and the rows start at display RAM row 0, so the display
+/// offset sent during initialisation is 0. With `Rotate180` the segment remap is flipped, and the
+/// same window is addressed from the other edge of the display RAM, starting at column
+/// 132 - 72 - 28 = 32.
+///
+/// ```rust
+/// # use sh1106::test_helpers::MockInterface;
+/// # let interface = MockInterface::new();
+/// use sh1106::{prelude::*, Sh1106};
+///
+/// let mut display = Sh1106::new(interface, DisplaySize72x40, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+/// display.init().unwrap();
+///
+/// let interface = display.release();
+/// let commands = interface.commands();
+///
+/// // Multiplex ratio
+/// assert!(commands.windows(2).any(|c| c == [0xA8, 0x27]));
+/// // Display offset
+/// assert!(commands.windows(2).any(|c| c == [0xD3, 0x00]));
+///
+/// for (rotation, column) in [
+///     (DisplayRotation::Rotate0, [0x0C, 0x11]),
+///     (DisplayRotation::Rotate180, [0x00, 0x12]),
+/// ] {
+///     let mut display = Sh1106::new(MockInterface::new(), DisplaySize72x40, rotation)
+///         .into_buffered_graphics_mode();
+///
+///     display.set_pixel(0, 0, true);
+///     display.flush().unwrap();
+///
+///     // Page 0, then the first column of the window
+///     assert_eq!(display.release().commands(), &[0xB0, column[0], column[1]]);
+/// }
+/// ```
 #[derive(Debug, Copy, Clone)]
 pub struct DisplaySize72x40;
 #[maybe_async_cfg::maybe(sync(keep_self), async(feature = "async", keep_self))]
@@ -132,8 +286,11 @@ impl DisplaySize for DisplaySize72x40 {
     const HEIGHT: u8 = 40;
     const OFFSETX: u8 = 28;
     const OFFSETY: u8 = 0;
-    type Buffer = [u8; <Self as DisplaySize>::WIDTH as usize *
-        <Self as DisplaySize>::HEIGHT as usize / 8];
+    type Buffer = [u8; <Self as DisplaySize>::BUFFER_LEN];
+
+    fn default_contrast() -> u8 {
+        0x7F
+    }
 
     async fn configure(
         &self,
@@ -144,6 +301,39 @@ impl DisplaySize for DisplaySize72x40 {
 }
 
 /// Size information for the common 64x48 variants
+///
+/// The visible window of these panels is usually centered in the 132 columns of display RAM,
+/// so the column offset defaults to 32. Some vendors center the window differently, which can be
+/// corrected with [`Sh1106::with_column_offset`](crate::Sh1106::with_column_offset).
+///
+/// ```rust
+/// # use sh1106::test_helpers::MockInterface;
+/// # let interface = MockInterface::new();
+/// use sh1106::{prelude::*, Sh1106};
+///
+/// let mut display = Sh1106::new(interface, DisplaySize64x48, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+/// display.init().unwrap();
+///
+/// let interface = display.release();
+/// let commands = interface.commands();
+///
+/// // Multiplex ratio
+/// assert!(commands.windows(2).any(|c| c == [0xA8, 0x2F]));
+/// // Display offset
+/// assert!(commands.windows(2).any(|c| c == [0xD3, 0x00]));
+///
+/// # let interface = MockInterface::new();
+/// let mut display = Sh1106::new(interface, DisplaySize64x48, DisplayRotation::Rotate0)
+///     .with_column_offset(30)
+///     .into_buffered_graphics_mode();
+///
+/// display.set_pixel(0, 0, true);
+/// display.flush().unwrap();
+///
+/// // Page 0, column 30
+/// assert_eq!(display.release().commands(), &[0xB0, 0x0E, 0x11]);
+/// ```
 #[derive(Debug, Copy, Clone)]
 pub struct DisplaySize64x48;
 #[maybe_async_cfg::maybe(sync(keep_self), async(feature = "async", keep_self))]
@@ -152,8 +342,11 @@ impl DisplaySize for DisplaySize64x48 {
     const HEIGHT: u8 = 48;
     const OFFSETX: u8 = 32;
     const OFFSETY: u8 = 0;
-    type Buffer = [u8; <Self as DisplaySize>::WIDTH as usize *
-        <Self as DisplaySize>::HEIGHT as usize / 8];
+    type Buffer = [u8; <Self as DisplaySize>::BUFFER_LEN];
+
+    fn default_contrast() -> u8 {
+        0x7F
+    }
 
     async fn configure(
         &self,
@@ -172,8 +365,11 @@ impl DisplaySize for DisplaySize64x32 {
     const HEIGHT: u8 = 32;
     const OFFSETX: u8 = 32;
     const OFFSETY: u8 = 0;
-    type Buffer = [u8; <Self as DisplaySize>::WIDTH as usize *
-        <Self as DisplaySize>::HEIGHT as usize / 8];
+    type Buffer = [u8; <Self as DisplaySize>::BUFFER_LEN];
+
+    fn default_contrast() -> u8 {
+        0x7F
+    }
 
     async fn configure(
         &self,
@@ -183,4 +379,111 @@ impl DisplaySize for DisplaySize64x32 {
     }
 }
 
+/// Size information for 128x128 modules with an SH1107 controller
+///
+/// Many 128x128 modules sold as SH1106 displays use the SH1107, which has 128 columns and 128
+/// rows of display RAM. It takes most of the SH1106 commands, but has 16 pages, sets the display
+/// start line with `0xDC` and has no COM pin configuration. Initialisation selects page
+/// addressing with `0x20` and sets the start line with `0xDC 0x00`, and flushes use
+/// [`Sh1107Addressing`] to reach all 16 pages.
+///
+/// This is available with the `sh1107` feature. The buffered graphics and console modes,
+/// [`set_row`](crate::Sh1106::set_row) and [`set_start_line`](crate::Sh1106::set_start_line)
+/// reach all 128 rows. Some parts of the driver are still limited to the SH1106:
+///
+/// - [`RawMode`](crate::mode::RawMode) addresses pages with [`Page`](crate::command::Page),
+///   which only has the first 8 pages, so its draw area can't reach past row 63.
+/// - The terminal mode isn't available, as the 256 characters don't fit its character count.
+///
+/// ```rust
+/// # use sh1106::test_helpers::MockInterface;
+/// # let interface = MockInterface::new();
+/// use sh1106::{prelude::*, size::DisplaySize128x128, Sh1106};
+///
+/// let mut display = Sh1106::new(interface, DisplaySize128x128, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+/// display.init_default().unwrap();
+///
+/// #[rustfmt::skip]
+/// assert_eq!(
+///     display.release().commands(),
+///     &[
+///         0xAE,       // Display off
+///         0xD5, 0x50, // Display clock
+///         0xA8, 0x7F, // Multiplex ratio
+///         0xD3, 0x00, // Display offset
+///         0x40,       // Start line, ignored by the SH1107
+///         0xAD, 0x8B, // DC-DC converter on
+///         0x20,       // Page addressing
+///         0xDC, 0x00, // Start line
+///         0xA1,       // Segment remap
+///         0xC8,       // Reverse COM scan direction
+///         0xD9, 0x21, // Pre-charge period
+///         0x81, 0x5F, // Contrast
+///         0xDB, 0x50, // VCOMH deselect level
+///         0xA4,       // Resume to RAM content
+///         0xA6,       // Normal display
+///         0xAF,       // Display on
+///     ]
+/// );
+///
+/// # let interface = MockInterface::new();
+/// let mut display = Sh1106::new(interface, DisplaySize128x128, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// display.set_pixel(0, 127, true);
+/// display.flush().unwrap();
+///
+/// // Page 15, column 0
+/// assert_eq!(display.release().commands(), &[0xBF, 0x00, 0x10]);
+///
+/// // Scrolling moves rows between all 16 pages
+/// # let interface = MockInterface::new();
+/// let mut display = Sh1106::new(interface, DisplaySize128x128, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// display.set_pixel(0, 127, true);
+/// display.scroll_up(1);
+/// assert_eq!(display.buffer()[15 * 128], 0b0100_0000);
+///
+/// display.scroll_up(68);
+/// assert_eq!(display.buffer()[7 * 128], 0b0000_0100);
+///
+/// display.scroll_down(69);
+/// assert_eq!(display.buffer()[15 * 128], 0b1000_0000);
+/// assert_eq!(display.buffer().iter().filter(|byte| **byte != 0).count(), 1);
+///
+/// // The start line is sent with `0xDC` and goes up to row 127
+/// # let interface = MockInterface::new();
+/// let mut display = Sh1106::new(interface, DisplaySize128x128, DisplayRotation::Rotate0);
+///
+/// display.set_start_line(100).unwrap();
+/// display.set_start_line(200).unwrap();
+/// assert_eq!(display.start_line(), 127);
+/// assert_eq!(display.release().commands(), &[0xDC, 100, 0xDC, 127]);
+/// ```
+#[cfg(feature = "sh1107")]
+#[derive(Debug, Copy, Clone)]
+pub struct DisplaySize128x128;
+#[cfg(feature = "sh1107")]
+#[maybe_async_cfg::maybe(sync(keep_self), async(feature = "async", keep_self))]
+impl DisplaySize for DisplaySize128x128 {
+    const WIDTH: u8 = 128;
+    const HEIGHT: u8 = 128;
+    const DRIVER_COLS: u8 = 128;
+    const DRIVER_ROWS: u8 = 128;
+    type Buffer = [u8; <Self as DisplaySize>::BUFFER_LEN];
+
+    fn addressing() -> &'static dyn AddressingStrategy {
+        &Sh1107Addressing
+    }
+
+    async fn configure(
+        &self,
+        iface: &mut impl WriteOnlyDataCommand,
+    ) -> Result<(), DisplayError> {
+        iface.send_commands(DataFormat::U8(&[0x20, 0xDC, 0x00])).await
+    }
+}
+
 } // content
@@ -0,0 +1,379 @@
+//! SPI interface factory
+
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{
+    DataFormat::{self, U8Iter, U8},
+    DisplayError, WriteOnlyDataCommand,
+};
+use display_interface_spi::SPIInterface;
+use embedded_hal::{
+    digital::OutputPin,
+    spi::{SpiBus, SpiDevice},
+};
+#[cfg(feature = "async")]
+use embedded_hal_async::spi::{SpiBus as SpiBusAsync, SpiDevice as SpiDeviceAsync};
+
+/// Helper struct to create preconfigured 4-wire SPI interfaces for the display.
+///
+/// The display's D0 (SCK), D1 (MOSI) and CS pins are driven by the SPI device, and the D/C pin
+/// selects whether each transfer is a command or data.
+#[derive(Debug, Copy, Clone)]
+pub struct SPIDisplayInterface(());
+
+impl SPIDisplayInterface {
+    /// Create a new 4-wire SPI interface from an SPI device and a D/C pin.
+    ///
+    /// The SPI device is responsible for the chip select pin, for example by using
+    /// `embedded_hal_bus::spi::ExclusiveDevice`.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<SPI, DC>(spi: SPI, dc: DC) -> SPIInterface<SPI, DC>
+    where
+        DC: OutputPin,
+    {
+        SPIInterface::new(spi, dc)
+    }
+
+    /// Create a new 4-wire SPI interface from an SPI bus, a D/C pin and a CS pin driven by the
+    /// interface.
+    ///
+    /// Prefer [`new`](SPIDisplayInterface::new) with an `SpiDevice` when the HAL or
+    /// `embedded-hal-bus` provides one. See [`SPIBusInterface`] for when this is needed instead.
+    pub fn new_with_cs<SPI, DC, CS>(spi: SPI, dc: DC, cs: CS) -> SPIBusInterface<SPI, DC, CS>
+    where
+        DC: OutputPin,
+        CS: OutputPin,
+    {
+        SPIBusInterface::new(spi, dc, cs)
+    }
+
+    /// Create a new 3-wire SPI interface from an SPI device sending 9 bit words.
+    ///
+    /// See [`SPIInterface3Wire`] for the requirements on the SPI device.
+    pub fn new_3wire<SPI>(spi: SPI) -> SPIInterface3Wire<SPI> {
+        SPIInterface3Wire::new(spi)
+    }
+}
+
+/// Number of words encoded before they are written to the SPI device
+const WORDS_PER_WRITE: usize = 32;
+
+/// A 4-wire SPI interface on an SPI bus, which drives the CS pin itself.
+///
+/// CS is pulled low before each group of commands or data and released once the bus has
+/// finished sending it, with the D/C pin set just before CS is pulled low. If the transfer fails,
+/// CS is still released and the error of the transfer is returned. Use this when only bus-level
+/// access to the SPI peripheral is available, for example because the HAL has no `SpiDevice` and
+/// the bus isn't shared. Otherwise prefer [`SPIDisplayInterface::new`] with an `SpiDevice`,
+/// which handles CS, and the bus sharing and timing around it, for you.
+///
+/// ```rust
+/// # use sh1106::test_helpers::{PinStub, SpiStub};
+/// # let (spi, dc, cs) = (SpiStub, PinStub, PinStub);
+/// use sh1106::{prelude::*, SPIDisplayInterface, Sh1106};
+///
+/// let interface = SPIDisplayInterface::new_with_cs(spi, dc, cs);
+/// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// display.init().unwrap();
+/// display.flush().unwrap();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct SPIBusInterface<SPI, DC, CS> {
+    spi: SPI,
+    dc: DC,
+    cs: CS,
+}
+
+impl<SPI, DC, CS> SPIBusInterface<SPI, DC, CS>
+where
+    DC: OutputPin,
+    CS: OutputPin,
+{
+    /// Create a new SPI interface from an SPI bus, a D/C pin and a CS pin.
+    pub fn new(spi: SPI, dc: DC, cs: CS) -> Self {
+        Self { spi, dc, cs }
+    }
+
+    /// Release the SPI bus, D/C pin and CS pin.
+    pub fn release(self) -> (SPI, DC, CS) {
+        (self.spi, self.dc, self.cs)
+    }
+
+    /// Set the D/C pin for a command or data transfer and pull CS low.
+    ///
+    /// D/C is set first, so it's stable once the display is selected and CS is left high if
+    /// setting it fails.
+    fn select(&mut self, data: bool) -> Result<(), DisplayError> {
+        self.dc
+            .set_state(data.into())
+            .map_err(|_| DisplayError::DCError)?;
+        self.cs.set_low().map_err(|_| DisplayError::CSError)
+    }
+
+    /// Release CS after a transfer, returning the error of the transfer if it failed, and
+    /// otherwise the error of releasing CS.
+    fn deselect(&mut self, result: Result<(), DisplayError>) -> Result<(), DisplayError> {
+        let released = self.cs.set_high().map_err(|_| DisplayError::CSError);
+
+        result.and(released)
+    }
+}
+
+impl<SPI, DC, CS> SPIBusInterface<SPI, DC, CS>
+where
+    SPI: SpiBus,
+    DC: OutputPin,
+    CS: OutputPin,
+{
+    fn send(&mut self, data: bool, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.select(data)?;
+        let result = write_bus(&mut self.spi, buf);
+
+        self.deselect(result)
+    }
+}
+
+/// Copy the next bytes into `chunk`, returning the number of bytes copied.
+fn fill_chunk(bytes: &mut dyn Iterator<Item = u8>, chunk: &mut [u8]) -> usize {
+    chunk
+        .iter_mut()
+        .zip(bytes)
+        .map(|(byte, next)| *byte = next)
+        .count()
+}
+
+/// Write `buf` to the SPI bus and wait for the bus to finish sending it.
+fn write_bus<SPI: SpiBus>(spi: &mut SPI, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+    match buf {
+        U8(bytes) => spi.write(bytes).map_err(|_| DisplayError::BusWriteError)?,
+        U8Iter(bytes) => {
+            let mut chunk = [0; WORDS_PER_WRITE];
+
+            loop {
+                match fill_chunk(bytes, &mut chunk) {
+                    0 => break,
+                    len => spi
+                        .write(&chunk[..len])
+                        .map_err(|_| DisplayError::BusWriteError)?,
+                }
+            }
+        }
+        _ => return Err(DisplayError::DataFormatNotImplemented),
+    }
+
+    spi.flush().map_err(|_| DisplayError::BusWriteError)
+}
+
+impl<SPI, DC, CS> WriteOnlyDataCommand for SPIBusInterface<SPI, DC, CS>
+where
+    SPI: SpiBus,
+    DC: OutputPin,
+    CS: OutputPin,
+{
+    fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(false, cmds)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(true, buf)
+    }
+}
+
+/// Write `buf` to the async SPI bus and wait for the bus to finish sending it.
+#[cfg(feature = "async")]
+async fn write_bus_async<SPI: SpiBusAsync>(
+    spi: &mut SPI,
+    buf: DataFormat<'_>,
+) -> Result<(), DisplayError> {
+    match buf {
+        U8(bytes) => spi
+            .write(bytes)
+            .await
+            .map_err(|_| DisplayError::BusWriteError)?,
+        U8Iter(bytes) => {
+            let mut chunk = [0; WORDS_PER_WRITE];
+
+            loop {
+                match fill_chunk(bytes, &mut chunk) {
+                    0 => break,
+                    len => spi
+                        .write(&chunk[..len])
+                        .await
+                        .map_err(|_| DisplayError::BusWriteError)?,
+                }
+            }
+        }
+        _ => return Err(DisplayError::DataFormatNotImplemented),
+    }
+
+    spi.flush().await.map_err(|_| DisplayError::BusWriteError)
+}
+
+#[cfg(feature = "async")]
+impl<SPI, DC, CS> AsyncWriteOnlyDataCommand for SPIBusInterface<SPI, DC, CS>
+where
+    SPI: SpiBusAsync,
+    DC: OutputPin,
+    CS: OutputPin,
+{
+    async fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.select(false)?;
+        let result = write_bus_async(&mut self.spi, cmds).await;
+
+        self.deselect(result)
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.select(true)?;
+        let result = write_bus_async(&mut self.spi, buf).await;
+
+        self.deselect(result)
+    }
+}
+
+/// A 3-wire SPI interface, for panels without a D/C pin.
+///
+/// In 3-wire mode the display has no D/C pin, and every byte is instead sent as a 9 bit word
+/// whose first bit selects whether the byte is a command (0) or data (1), followed by the byte
+/// itself, most significant bit first. The display's D0 (SCK), D1 (MOSI) and CS pins are driven by
+/// the SPI device, which is responsible for the chip select pin.
+///
+/// Each word is passed to the SPI device as a `u16`, holding the D/C bit in bit 8 and the byte
+/// in bits 0 to 7. The SPI device must send exactly the low 9 bits of every word in a 9 bit frame,
+/// MSB first in SPI mode 0 or 3. Not every SPI peripheral supports 9 bit frames; with one limited
+/// to 8 or 16 bit frames, use a bit-banged SPI device or an interface with a D/C pin instead.
+///
+/// ```rust
+/// # use sh1106::test_helpers::Spi9BitMock;
+/// use sh1106::{prelude::*, SPIDisplayInterface, Sh1106};
+///
+/// let interface = SPIDisplayInterface::new_3wire(Spi9BitMock::new());
+/// let mut display = Sh1106::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// display.set_pixel(0, 0, true);
+/// display.flush().unwrap();
+///
+/// // The page and column address are commands, followed by one data byte
+/// assert_eq!(
+///     display.release().release().words(),
+///     &[0x0B0, 0x002, 0x010, 0x101]
+/// );
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct SPIInterface3Wire<SPI> {
+    spi: SPI,
+}
+
+impl<SPI> SPIInterface3Wire<SPI> {
+    /// Create a new 3-wire SPI interface from an SPI device sending 9 bit words.
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    /// Release the SPI device.
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+}
+
+/// Encode the next bytes as 9 bit words with the D/C bit set for data, returning the number of
+/// words written to `words`.
+fn encode(data: bool, bytes: &mut dyn Iterator<Item = u8>, words: &mut [u16]) -> usize {
+    let dc = u16::from(data) << 8;
+
+    words
+        .iter_mut()
+        .zip(bytes)
+        .map(|(word, byte)| *word = dc | u16::from(byte))
+        .count()
+}
+
+impl<SPI> SPIInterface3Wire<SPI>
+where
+    SPI: SpiDevice<u16>,
+{
+    fn send(&mut self, data: bool, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        let mut slice;
+        let bytes: &mut dyn Iterator<Item = u8> = match buf {
+            U8(bytes) => {
+                slice = bytes.iter().copied();
+                &mut slice
+            }
+            U8Iter(bytes) => bytes,
+            _ => return Err(DisplayError::DataFormatNotImplemented),
+        };
+
+        let mut words = [0; WORDS_PER_WRITE];
+
+        loop {
+            match encode(data, bytes, &mut words) {
+                0 => return Ok(()),
+                len => self
+                    .spi
+                    .write(&words[..len])
+                    .map_err(|_| DisplayError::BusWriteError)?,
+            }
+        }
+    }
+}
+
+impl<SPI> WriteOnlyDataCommand for SPIInterface3Wire<SPI>
+where
+    SPI: SpiDevice<u16>,
+{
+    fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(false, cmds)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send(true, buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<SPI> SPIInterface3Wire<SPI>
+where
+    SPI: SpiDeviceAsync<u16>,
+{
+    async fn send_async(&mut self, data: bool, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        let mut slice;
+        let bytes: &mut dyn Iterator<Item = u8> = match buf {
+            U8(bytes) => {
+                slice = bytes.iter().copied();
+                &mut slice
+            }
+            U8Iter(bytes) => bytes,
+            _ => return Err(DisplayError::DataFormatNotImplemented),
+        };
+
+        let mut words = [0; WORDS_PER_WRITE];
+
+        loop {
+            match encode(data, bytes, &mut words) {
+                0 => return Ok(()),
+                len => self
+                    .spi
+                    .write(&words[..len])
+                    .await
+                    .map_err(|_| DisplayError::BusWriteError)?,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<SPI> AsyncWriteOnlyDataCommand for SPIInterface3Wire<SPI>
+where
+    SPI: SpiDeviceAsync<u16>,
+{
+    async fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send_async(false, cmds).await
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.send_async(true, buf).await
+    }
+}
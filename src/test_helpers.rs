@@ -1,12 +1,35 @@
-//! Helpers for use in examples and tests
+//! Mock interfaces and stubs for testing on the host, enabled by the `mock` feature.
+//!
+//! [`MockInterface`] records every command and data byte sent to it, so the exact byte stream
+//! emitted by the driver can be checked on the host with `cargo test`, in this crate and in
+//! crates using it. It also implements [`RmwInterface`], reading back a fixed value for every
+//! display RAM byte. The bytes are stored in a `Vec`, so the feature needs `alloc`.
+//!
+//! The other types are stubs which accept and ignore everything, for drivers and examples that
+//! need an interface, pin or delay but don't check what is sent to it.
+//!
+//! To use them in the tests of another crate, enable the feature for the dev-dependency only:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! sh1106 = { version = "0.1.0", features = ["mock"] }
+//! ```
 
-use display_interface::{DisplayError, WriteOnlyDataCommand};
+use crate::{ReadableInterface, RmwInterface};
+#[cfg(feature = "async")]
+use crate::{ReadableInterfaceAsync, RmwInterfaceAsync};
+use alloc::vec::Vec;
+#[cfg(feature = "async")]
+use display_interface::AsyncWriteOnlyDataCommand;
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
 use embedded_hal::{
+    delay::DelayNs,
     digital::{ErrorType, OutputPin},
     i2c,
-    spi::{self, SpiBus},
+    spi::{self, Operation, SpiBus, SpiDevice},
 };
 
+/// Error of the stubs, which are never returned by the stubs themselves
 #[derive(PartialEq, Eq, Clone, Debug, Copy)]
 pub struct Error {}
 
@@ -28,7 +51,7 @@ impl spi::Error for Error {
     }
 }
 
-#[allow(dead_code)]
+/// SPI bus which ignores everything written to it
 #[derive(Debug, Clone, Copy)]
 pub struct SpiStub;
 
@@ -54,11 +77,56 @@ impl SpiBus<u8> for SpiStub {
     }
 
     fn flush(&mut self) -> Result<(), Self::Error> {
-        todo!()
+        Ok(())
     }
 }
 
-#[allow(dead_code)]
+/// SPI device which records the 9 bit words written to it
+#[derive(Debug, Clone, Default)]
+pub struct Spi9BitMock {
+    words: Vec<u16>,
+}
+
+impl Spi9BitMock {
+    /// Create a mock which hasn't recorded any words yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All words written so far
+    pub fn words(&self) -> &[u16] {
+        &self.words
+    }
+
+    fn record(&mut self, operations: &mut [Operation<'_, u16>]) -> Result<(), Error> {
+        for operation in operations {
+            if let Operation::Write(words) = operation {
+                self.words.extend_from_slice(words);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl spi::ErrorType for Spi9BitMock {
+    type Error = Error;
+}
+
+impl SpiDevice<u16> for Spi9BitMock {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u16>]) -> Result<(), Error> {
+        self.record(operations)
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::spi::SpiDevice<u16> for Spi9BitMock {
+    async fn transaction(&mut self, operations: &mut [Operation<'_, u16>]) -> Result<(), Error> {
+        self.record(operations)
+    }
+}
+
+/// I2C bus which ignores every transaction
 #[derive(Debug, Clone, Copy)]
 pub struct I2cStub;
 
@@ -76,7 +144,7 @@ impl i2c::I2c for I2cStub {
     }
 }
 
-#[allow(dead_code)]
+/// Output pin which ignores every change of state
 #[derive(Debug, Clone, Copy)]
 pub struct PinStub;
 
@@ -94,18 +162,170 @@ impl OutputPin for PinStub {
     }
 }
 
-#[allow(dead_code)]
+/// Delay which returns immediately
+#[derive(Debug, Clone, Copy)]
+pub struct DelayStub;
+
+impl DelayNs for DelayStub {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Display interface which ignores every command and data byte
 #[derive(Debug, Clone, Copy)]
 pub struct StubInterface;
 
 impl WriteOnlyDataCommand for StubInterface {
-    fn send_commands(
-        &mut self,
-        _cmd: display_interface::DataFormat<'_>,
-    ) -> Result<(), DisplayError> {
+    fn send_commands(&mut self, _cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        Ok(())
+    }
+    fn send_data(&mut self, _buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        Ok(())
+    }
+}
+
+/// Interface which records all command and data bytes sent to it
+///
+/// ```rust
+/// use sh1106::{prelude::*, test_helpers::MockInterface, Sh1106};
+///
+/// let mut display = Sh1106::new(MockInterface::new(), DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode();
+///
+/// display.set_pixel(0, 0, true);
+/// display.flush().unwrap();
+///
+/// let interface = display.release();
+///
+/// // Page 0, then column 2 as the SH1106 RAM is 132 columns wide
+/// assert_eq!(interface.commands(), &[0xB0, 0x02, 0x10]);
+/// assert_eq!(interface.data(), &[0x01]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockInterface {
+    commands: Vec<u8>,
+    data: Vec<u8>,
+    read_value: u8,
+    status: u8,
+    data_failures: u8,
+}
+
+impl MockInterface {
+    /// Create a mock which hasn't recorded any bytes yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All command bytes sent so far
+    pub fn commands(&self) -> &[u8] {
+        &self.commands
+    }
+
+    /// All data bytes sent so far
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Set the value returned for every byte read from the display RAM
+    pub fn set_read_value(&mut self, value: u8) {
+        self.read_value = value;
+    }
+
+    /// Set the status byte returned by [`ReadableInterface::read_status`]
+    pub fn set_status(&mut self, status: u8) {
+        self.status = status;
+    }
+
+    /// Make the next `count` data transfers fail with [`DisplayError::BusWriteError`]
+    pub fn fail_data(&mut self, count: u8) {
+        self.data_failures = count;
+    }
+
+    /// Forget all bytes sent so far
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.data.clear();
+    }
+
+    fn record(dest: &mut Vec<u8>, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        match buf {
+            DataFormat::U8(slice) => {
+                dest.extend_from_slice(slice);
+
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+
+    fn record_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        if self.data_failures > 0 {
+            self.data_failures -= 1;
+
+            return Err(DisplayError::BusWriteError);
+        }
+
+        Self::record(&mut self.data, buf)
+    }
+}
+
+impl WriteOnlyDataCommand for MockInterface {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        Self::record(&mut self.commands, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.record_data(buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncWriteOnlyDataCommand for MockInterface {
+    async fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        Self::record(&mut self.commands, cmd)
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.record_data(buf)
+    }
+}
+
+/// Borrowing the mock, so the bytes sent can be checked after the driver is dropped
+impl WriteOnlyDataCommand for &mut MockInterface {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        WriteOnlyDataCommand::send_commands(*self, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        WriteOnlyDataCommand::send_data(*self, buf)
+    }
+}
+
+impl RmwInterface for MockInterface {
+    fn read_data(&mut self, buf: &mut [u8]) -> Result<(), DisplayError> {
+        buf.fill(self.read_value);
+
         Ok(())
     }
-    fn send_data(&mut self, _buf: display_interface::DataFormat<'_>) -> Result<(), DisplayError> {
+}
+
+#[cfg(feature = "async")]
+impl RmwInterfaceAsync for MockInterface {
+    async fn read_data(&mut self, buf: &mut [u8]) -> Result<(), DisplayError> {
+        buf.fill(self.read_value);
+
         Ok(())
     }
 }
+
+impl ReadableInterface for MockInterface {
+    fn read_status(&mut self) -> Result<u8, DisplayError> {
+        Ok(self.status)
+    }
+}
+
+#[cfg(feature = "async")]
+impl ReadableInterfaceAsync for MockInterface {
+    async fn read_status(&mut self) -> Result<u8, DisplayError> {
+        Ok(self.status)
+    }
+}